@@ -1,3 +1,7 @@
+use std::iter::FusedIterator;
+
+use crate::error::{Error, Result};
+
 /// Splits an iterable collection into consecutive chunks of at most `size` items, preserving order.
 ///
 /// The last chunk may contain fewer than `size` items if there are not enough elements remaining.
@@ -29,22 +33,292 @@
 ///
 /// Panics if `size` is 0.
 pub fn chunk<T>(items: impl IntoIterator<Item = T>, size: usize) -> Vec<Vec<T>> {
-    assert!(size > 0, "size must be greater than 0");
+    try_chunk(items, size).unwrap_or_else(|err| panic!("{err}"))
+}
 
-    let mut iter = items.into_iter();
+/// Splits an iterable collection into consecutive chunks of at most `size` items,
+/// returning an error instead of panicking when `size` is 0.
+///
+/// This is the fallible counterpart of [`chunk`], useful when the chunk size
+/// comes from configuration or user input.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `size` - The maximum size of each chunk
+///
+/// # Returns
+///
+/// `Ok` with the chunks, or [`Error::ZeroSize`] if `size` is 0
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{Error, try_chunk};
+///
+/// assert_eq!(try_chunk(vec![1, 2, 3], 2), Ok(vec![vec![1, 2], vec![3]]));
+/// assert_eq!(try_chunk(vec![1, 2, 3], 0), Err(Error::ZeroSize));
+/// ```
+pub fn try_chunk<T>(items: impl IntoIterator<Item = T>, size: usize) -> Result<Vec<Vec<T>>> {
+    Ok(try_chunks_lazy(items, size)?.collect())
+}
+
+/// How [`chunk_by_weight`] handles a single item that is heavier than the maximum
+/// weight of a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OversizedPolicy {
+    /// Put the item in a chunk of its own.
+    #[default]
+    OwnChunk,
+    /// Stop and return [`Error::WeightExceeded`].
+    Error,
+    /// Skip the item.
+    Drop,
+}
+
+/// Splits an iterable collection into consecutive chunks whose total weight does not
+/// exceed `max_weight`, preserving order.
+///
+/// Items are added to the current chunk until the next item would push its total
+/// weight over `max_weight`, at which point a new chunk is started. Items that are
+/// heavier than `max_weight` on their own are handled according to `policy`.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `max_weight` - The maximum total weight of each chunk
+/// * `weight_resolver` - A function that takes a reference to an item and returns its weight
+/// * `policy` - What to do with an item whose weight exceeds `max_weight`
+///
+/// # Returns
+///
+/// `Ok` with the chunks, or [`Error::WeightExceeded`] if an item is too heavy and
+/// `policy` is [`OversizedPolicy::Error`]
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{OversizedPolicy, chunk_by_weight};
+///
+/// let payloads = vec!["aaaa", "bb", "cc", "dddddddd", "e"];
+/// let batches = chunk_by_weight(payloads, 5, |p| p.len(), OversizedPolicy::OwnChunk).unwrap();
+/// assert_eq!(batches, vec![vec!["aaaa"], vec!["bb", "cc"], vec!["dddddddd"], vec!["e"]]);
+///
+/// let batches = chunk_by_weight(vec!["aaaa", "bb", "dddddddd"], 5, |p| p.len(), OversizedPolicy::Drop);
+/// assert_eq!(batches, Ok(vec![vec!["aaaa"], vec!["bb"]]));
+/// ```
+pub fn chunk_by_weight<T>(
+    items: impl IntoIterator<Item = T>,
+    max_weight: usize,
+    weight_resolver: impl Fn(&T) -> usize,
+    policy: OversizedPolicy,
+) -> Result<Vec<Vec<T>>> {
     let mut chunks: Vec<Vec<T>> = Vec::new();
+    let mut current: Vec<T> = Vec::new();
+    let mut current_weight = 0;
+
+    for (index, item) in items.into_iter().enumerate() {
+        let weight = weight_resolver(&item);
+
+        if weight > max_weight {
+            match policy {
+                OversizedPolicy::OwnChunk => {
+                    if !current.is_empty() {
+                        chunks.push(std::mem::take(&mut current));
+                        current_weight = 0;
+                    }
+                    chunks.push(vec![item]);
+                }
+                OversizedPolicy::Error => {
+                    return Err(Error::WeightExceeded {
+                        index,
+                        weight,
+                        max_weight,
+                    });
+                }
+                OversizedPolicy::Drop => {}
+            }
+            continue;
+        }
+
+        // `current_weight` never exceeds `max_weight`, so this cannot underflow
+        if weight > max_weight - current_weight {
+            chunks.push(std::mem::take(&mut current));
+            current_weight = 0;
+        }
+        current.push(item);
+        current_weight += weight;
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    Ok(chunks)
+}
+
+/// Splits an iterable collection into fixed-size `[T; N]` chunks, returning the
+/// leftover items separately.
+///
+/// Every full chunk is moved into an array, so no `Vec` is allocated per chunk.
+/// Items that do not fill a final chunk are returned as the remainder.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+///
+/// # Returns
+///
+/// A tuple `(chunks, remainder)` where `chunks` holds every full `[T; N]` chunk in
+/// input order and `remainder` holds the fewer than `N` trailing items
+///
+/// # Type Parameters
+///
+/// * `N` - The size of each chunk (must be greater than 0; checked at compile time)
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::chunk_array;
+///
+/// let (chunks, remainder) = chunk_array::<_, 2>(vec![1, 2, 3, 4, 5]);
+/// assert_eq!(chunks, vec![[1, 2], [3, 4]]);
+/// assert_eq!(remainder, vec![5]);
+/// ```
+pub fn chunk_array<T, const N: usize>(items: impl IntoIterator<Item = T>) -> (Vec<[T; N]>, Vec<T>) {
+    const { assert!(N > 0, "N must be greater than 0") };
+
+    let iter = items.into_iter();
+    let mut chunks: Vec<[T; N]> = Vec::with_capacity(iter.size_hint().0 / N);
+    // Reused for every chunk so only the output vector allocates.
+    let mut buffer: Vec<T> = Vec::with_capacity(N);
+
+    for item in iter {
+        buffer.push(item);
+        if buffer.len() == N {
+            let mut drain = buffer.drain(..);
+            chunks.push(std::array::from_fn(|_| {
+                drain.next().expect("buffer holds exactly N items")
+            }));
+        }
+    }
+
+    (chunks, buffer)
+}
+
+/// Lazily splits an iterable collection into consecutive chunks of at most `size` items.
+///
+/// Unlike [`chunk`], this does not collect every chunk up front: the returned
+/// [`Chunks`] iterator pulls at most `size` items from the source each time a
+/// chunk is requested, so only one chunk is held in memory at a time.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `size` - The maximum size of each chunk (must be greater than 0)
+///
+/// # Returns
+///
+/// A [`Chunks`] iterator yielding `Vec<T>` chunks in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::chunks_lazy;
+///
+/// let mut chunks = chunks_lazy(1..=5, 2);
+/// assert_eq!(chunks.next(), Some(vec![1, 2]));
+/// assert_eq!(chunks.next(), Some(vec![3, 4]));
+/// assert_eq!(chunks.next(), Some(vec![5]));
+/// assert_eq!(chunks.next(), None);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn chunks_lazy<I: IntoIterator>(items: I, size: usize) -> Chunks<I::IntoIter> {
+    try_chunks_lazy(items, size).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Lazily splits an iterable collection into consecutive chunks of at most `size` items,
+/// returning an error instead of panicking when `size` is 0.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `size` - The maximum size of each chunk
+///
+/// # Returns
+///
+/// `Ok` with a [`Chunks`] iterator, or [`Error::ZeroSize`] if `size` is 0
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::try_chunks_lazy;
+///
+/// let chunks: Vec<Vec<i32>> = try_chunks_lazy(1..=3, 2).unwrap().collect();
+/// assert_eq!(chunks, vec![vec![1, 2], vec![3]]);
+/// assert!(try_chunks_lazy(1..=3, 0).is_err());
+/// ```
+pub fn try_chunks_lazy<I: IntoIterator>(items: I, size: usize) -> Result<Chunks<I::IntoIter>> {
+    if size == 0 {
+        return Err(Error::ZeroSize);
+    }
+
+    Ok(Chunks {
+        iter: items.into_iter(),
+        size,
+        done: false,
+    })
+}
 
-    loop {
-        let part: Vec<T> = iter.by_ref().take(size).collect();
-        if part.is_empty() {
-            break;
+/// An iterator that lazily yields consecutive chunks of at most `size` items.
+///
+/// This struct is created by [`chunks_lazy`], [`try_chunks_lazy`] or
+/// [`ChunkExt::chunks_lazy`].
+/// Once the source is exhausted it keeps returning `None`, even if the
+/// underlying iterator is not fused.
+#[derive(Debug, Clone)]
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+    done: bool,
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let part: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if part.len() < self.size {
+            // A short (or empty) chunk means the source ran dry.
+            self.done = true;
         }
-        chunks.push(part);
+        if part.is_empty() { None } else { Some(part) }
     }
 
-    chunks
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.div_ceil(self.size),
+            upper.map(|upper| upper.div_ceil(self.size)),
+        )
+    }
 }
 
+impl<I: ExactSizeIterator> ExactSizeIterator for Chunks<I> {}
+
+impl<I: Iterator> FusedIterator for Chunks<I> {}
+
 /// Extension trait that adds the `chunk` method to any iterator.
 ///
 /// This trait provides a convenient way to split an iterator into consecutive
@@ -82,6 +356,140 @@ pub trait ChunkExt: Iterator {
     {
         chunk(self, size)
     }
+
+    /// Splits the iterator into consecutive chunks whose total weight does not
+    /// exceed `max_weight`.
+    ///
+    /// See [`chunk_by_weight`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_weight` - The maximum total weight of each chunk
+    /// * `weight_resolver` - A function that takes a reference to an item and returns its weight
+    /// * `policy` - What to do with an item whose weight exceeds `max_weight`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::{ChunkExt, Error, OversizedPolicy};
+    ///
+    /// let sizes = vec![3, 3, 9];
+    /// let result = sizes.into_iter().chunk_by_weight(6, |&n| n, OversizedPolicy::Error);
+    /// assert_eq!(
+    ///     result,
+    ///     Err(Error::WeightExceeded { index: 2, weight: 9, max_weight: 6 })
+    /// );
+    /// ```
+    fn chunk_by_weight(
+        self,
+        max_weight: usize,
+        weight_resolver: impl Fn(&Self::Item) -> usize,
+        policy: OversizedPolicy,
+    ) -> Result<Vec<Vec<Self::Item>>>
+    where
+        Self: Sized,
+    {
+        chunk_by_weight(self, max_weight, weight_resolver, policy)
+    }
+
+    /// Splits the iterator into fixed-size `[Self::Item; N]` chunks, returning the
+    /// leftover items separately.
+    ///
+    /// See [`chunk_array`] for details.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(chunks, remainder)` of the full chunks and the trailing items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ChunkExt;
+    ///
+    /// let (pairs, rest) = (1..=5).chunk_array::<2>();
+    /// assert_eq!(pairs, vec![[1, 2], [3, 4]]);
+    /// assert_eq!(rest, vec![5]);
+    /// ```
+    fn chunk_array<const N: usize>(self) -> (Vec<[Self::Item; N]>, Vec<Self::Item>)
+    where
+        Self: Sized,
+    {
+        chunk_array(self)
+    }
+
+    /// Lazily splits the iterator into consecutive chunks of at most `size` items.
+    ///
+    /// See [`chunks_lazy`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum size of each chunk (must be greater than 0)
+    ///
+    /// # Returns
+    ///
+    /// A [`Chunks`] iterator yielding `Vec<Self::Item>` chunks in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ChunkExt;
+    ///
+    /// let total: usize = (0..10).chunks_lazy(4).map(|batch| batch.len()).sum();
+    /// assert_eq!(total, 10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    fn chunks_lazy(self, size: usize) -> Chunks<Self>
+    where
+        Self: Sized,
+    {
+        chunks_lazy(self, size)
+    }
+
+    /// Splits the iterator into consecutive chunks of at most `size` items,
+    /// returning an error instead of panicking when `size` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum size of each chunk
+    ///
+    /// # Returns
+    ///
+    /// `Ok` with the chunks, or [`Error::ZeroSize`] if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::{ChunkExt, Error};
+    ///
+    /// assert_eq!((1..=3).try_chunk(2), Ok(vec![vec![1, 2], vec![3]]));
+    /// assert_eq!((1..=3).try_chunk(0), Err(Error::ZeroSize));
+    /// ```
+    fn try_chunk(self, size: usize) -> Result<Vec<Vec<Self::Item>>>
+    where
+        Self: Sized,
+    {
+        try_chunk(self, size)
+    }
+
+    /// Lazily splits the iterator into consecutive chunks of at most `size` items,
+    /// returning an error instead of panicking when `size` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum size of each chunk
+    ///
+    /// # Returns
+    ///
+    /// `Ok` with a [`Chunks`] iterator, or [`Error::ZeroSize`] if `size` is 0.
+    fn try_chunks_lazy(self, size: usize) -> Result<Chunks<Self>>
+    where
+        Self: Sized,
+    {
+        try_chunks_lazy(self, size)
+    }
 }
 
 /// Blanket implementation of `ChunkExt` for all iterator types.
@@ -106,18 +514,155 @@ mod tests {
     }
 
     #[test]
-    fn should_panic_with_zero_size() {
+    fn test_chunk_array() {
+        let (chunks, remainder) = chunk_array::<_, 3>(vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(chunks, vec![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(remainder, vec![7]);
+    }
+
+    #[test]
+    fn test_chunk_array_ext_exact() {
+        let words = vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+        ];
+        let (chunks, remainder) = words.into_iter().chunk_array::<2>();
+        assert_eq!(chunks, vec![["a", "b"], ["c", "d"]]);
+        assert!(remainder.is_empty());
+    }
+
+    #[test]
+    fn test_chunk_array_shorter_than_n() {
+        let (chunks, remainder) = (1..=2).chunk_array::<4>();
+        assert!(chunks.is_empty());
+        assert_eq!(remainder, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_chunk_by_weight() {
+        let items = vec![2, 2, 2, 5, 1, 4];
+        let chunks = chunk_by_weight(items, 5, |&n| n, OversizedPolicy::OwnChunk);
+        assert_eq!(chunks, Ok(vec![vec![2, 2], vec![2], vec![5], vec![1, 4]]));
+    }
+
+    #[test]
+    fn test_chunk_by_weight_oversized_policies() {
+        let items = vec![1, 7, 2, 3];
+
+        let own = items
+            .clone()
+            .into_iter()
+            .chunk_by_weight(4, |&n| n, OversizedPolicy::OwnChunk);
+        assert_eq!(own, Ok(vec![vec![1], vec![7], vec![2], vec![3]]));
+
+        let dropped = items
+            .clone()
+            .into_iter()
+            .chunk_by_weight(4, |&n| n, OversizedPolicy::Drop);
+        assert_eq!(dropped, Ok(vec![vec![1, 2], vec![3]]));
+
+        let error = items
+            .into_iter()
+            .chunk_by_weight(4, |&n| n, OversizedPolicy::Error);
+        assert_eq!(
+            error,
+            Err(Error::WeightExceeded {
+                index: 1,
+                weight: 7,
+                max_weight: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_chunk_by_weight_zero_weight_items() {
+        let chunks = chunk_by_weight(vec!["", "", "abc"], 3, |s| s.len(), OversizedPolicy::Error);
+        assert_eq!(chunks, Ok(vec![vec!["", "", "abc"]]));
+    }
+
+    #[test]
+    fn test_chunks_lazy() {
+        let chunks: Vec<Vec<i32>> = chunks_lazy(vec![1, 2, 3, 4, 5], 2).collect();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn test_chunks_lazy_ext_pulls_only_one_chunk() {
+        let pulled = std::cell::Cell::new(0);
+        let mut chunks = (1..=100)
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .chunks_lazy(10);
+        assert_eq!(chunks.next(), Some((1..=10).collect()));
+        assert_eq!(pulled.get(), 10);
+    }
+
+    #[test]
+    fn test_chunks_lazy_size_hint() {
+        let mut chunks = vec![1, 2, 3, 4, 5].into_iter().chunks_lazy(2);
+        assert_eq!(chunks.len(), 3);
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
+        chunks.next();
+        chunks.next();
+        assert_eq!(chunks.len(), 0);
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn test_chunks_lazy_is_fused() {
+        // An iterator that yields again after returning `None`.
+        let mut flip = false;
+        let source = std::iter::from_fn(move || {
+            flip = !flip;
+            flip.then_some(1)
+        });
+        let mut chunks = source.chunks_lazy(3);
+        assert_eq!(chunks.next(), Some(vec![1]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn test_try_chunk() {
         let items = vec![1, 2, 3, 4, 5];
+        let chunks = try_chunk(items, 3);
+        assert_eq!(chunks, Ok(vec![vec![1, 2, 3], vec![4, 5]]));
+    }
 
-        let result = std::panic::catch_unwind(|| chunk(items, 0));
-        assert!(result.is_err());
+    #[test]
+    fn should_error_with_zero_size() {
+        let items = vec![1, 2, 3, 4, 5];
+        assert_eq!(try_chunk(items, 0), Err(Error::ZeroSize));
     }
 
     #[test]
-    fn should_panic_with_zero_size_ext() {
+    fn should_error_with_zero_size_ext() {
         let items = vec![1, 2, 3, 4, 5];
+        assert_eq!(items.into_iter().try_chunk(0), Err(Error::ZeroSize));
+    }
 
-        let result = std::panic::catch_unwind(|| items.into_iter().chunk(0));
-        assert!(result.is_err());
+    #[test]
+    fn should_error_with_zero_size_lazy() {
+        assert!(matches!(try_chunks_lazy(1..=5, 0), Err(Error::ZeroSize)));
+        assert!(matches!((1..=5).try_chunks_lazy(0), Err(Error::ZeroSize)));
+    }
+
+    #[test]
+    #[should_panic(expected = "size must be greater than 0")]
+    fn should_panic_with_zero_size() {
+        chunk(vec![1, 2, 3, 4, 5], 0);
+    }
+
+    #[test]
+    fn test_chunk_by_weight_near_usize_max() {
+        let chunks = chunk_by_weight(
+            vec![1, usize::MAX],
+            usize::MAX,
+            |&w| w,
+            OversizedPolicy::Error,
+        );
+        assert_eq!(chunks, Ok(vec![vec![1], vec![usize::MAX]]));
     }
 }
@@ -0,0 +1,387 @@
+use std::iter::{Fuse, FusedIterator};
+
+/// Splits an iterable collection into runs of consecutive items that share the same key.
+///
+/// A new chunk starts every time the key returned by `key_resolver` differs from
+/// the key of the previous item. Unlike [`group_by`](crate::group_by), items with
+/// the same key that are not adjacent end up in different chunks, which makes
+/// this the right tool for sorted or time-ordered streams.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `key_resolver` - A function that takes a reference to an item and returns its key
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` of non-empty runs that preserves input order
+///
+/// # Type Parameters
+///
+/// * `T` - The element type
+/// * `K` - The key type (must implement `PartialEq`)
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::chunk_by;
+///
+/// let items = vec![1, 1, 2, 3, 3, 1];
+/// let runs = chunk_by(items, |&n| n);
+/// assert_eq!(runs, vec![vec![1, 1], vec![2], vec![3, 3], vec![1]]);
+/// ```
+pub fn chunk_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl FnMut(&T) -> K,
+) -> Vec<Vec<T>>
+where
+    K: PartialEq,
+{
+    chunk_by_lazy(items, key_resolver).collect()
+}
+
+/// Lazily splits an iterable collection into runs of consecutive items that share the same key.
+///
+/// See [`chunk_by`] for details. The returned [`ChunkBy`] iterator only buffers
+/// the current run plus the first item of the next one.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::chunk_by_lazy;
+///
+/// let days = vec![(1, "a"), (1, "b"), (2, "c")];
+/// let mut runs = chunk_by_lazy(days, |&(day, _)| day);
+/// assert_eq!(runs.next(), Some(vec![(1, "a"), (1, "b")]));
+/// assert_eq!(runs.next(), Some(vec![(2, "c")]));
+/// assert_eq!(runs.next(), None);
+/// ```
+pub fn chunk_by_lazy<I, K, F>(items: I, key_resolver: F) -> ChunkBy<I::IntoIter, K, F>
+where
+    I: IntoIterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    ChunkBy {
+        iter: items.into_iter().fuse(),
+        key_resolver,
+        pending: None,
+    }
+}
+
+/// Splits an iterable collection into runs, starting a new run wherever
+/// `should_split(previous, next)` returns `true` for two neighbouring items.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `should_split` - A predicate over each pair of adjacent items
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` of non-empty runs that preserves input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::split_when;
+///
+/// let timestamps = vec![1, 2, 3, 10, 11, 30];
+/// let sessions = split_when(timestamps, |a, b| b - a > 5);
+/// assert_eq!(sessions, vec![vec![1, 2, 3], vec![10, 11], vec![30]]);
+/// ```
+pub fn split_when<T>(
+    items: impl IntoIterator<Item = T>,
+    should_split: impl FnMut(&T, &T) -> bool,
+) -> Vec<Vec<T>> {
+    split_when_lazy(items, should_split).collect()
+}
+
+/// Lazily splits an iterable collection into runs, starting a new run wherever
+/// `should_split(previous, next)` returns `true`.
+///
+/// See [`split_when`] for details.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::split_when_lazy;
+///
+/// let mut runs = split_when_lazy(vec![1, 2, 4, 5], |a, b| b - a != 1);
+/// assert_eq!(runs.next(), Some(vec![1, 2]));
+/// assert_eq!(runs.next(), Some(vec![4, 5]));
+/// assert_eq!(runs.next(), None);
+/// ```
+pub fn split_when_lazy<I, F>(items: I, should_split: F) -> SplitWhen<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    SplitWhen {
+        iter: items.into_iter().fuse(),
+        should_split,
+        pending: None,
+    }
+}
+
+/// An iterator over runs of consecutive items that share the same key.
+///
+/// This struct is created by [`chunk_by_lazy`] or [`ChunkByExt::chunk_by_lazy`].
+#[derive(Debug, Clone)]
+pub struct ChunkBy<I: Iterator, K, F> {
+    iter: Fuse<I>,
+    key_resolver: F,
+    /// The first item of the next run together with its key.
+    pending: Option<(K, I::Item)>,
+}
+
+impl<I, K, F> Iterator for ChunkBy<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (current_key, first) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let item = self.iter.next()?;
+                ((self.key_resolver)(&item), item)
+            }
+        };
+
+        let mut run = vec![first];
+        for item in self.iter.by_ref() {
+            let key = (self.key_resolver)(&item);
+            if key != current_key {
+                self.pending = Some((key, item));
+                break;
+            }
+            run.push(item);
+        }
+        Some(run)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        run_size_hint(self.pending.is_some(), self.iter.size_hint())
+    }
+}
+
+impl<I, K, F> FusedIterator for ChunkBy<I, K, F>
+where
+    I: Iterator,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+}
+
+/// An iterator over runs split wherever a predicate between neighbours holds.
+///
+/// This struct is created by [`split_when_lazy`] or [`ChunkByExt::split_when_lazy`].
+#[derive(Debug, Clone)]
+pub struct SplitWhen<I: Iterator, F> {
+    iter: Fuse<I>,
+    should_split: F,
+    /// The first item of the next run.
+    pending: Option<I::Item>,
+}
+
+impl<I, F> Iterator for SplitWhen<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.pending.take().or_else(|| self.iter.next())?;
+
+        let mut run = vec![first];
+        for item in self.iter.by_ref() {
+            let previous = run.last().expect("run is never empty");
+            if (self.should_split)(previous, &item) {
+                self.pending = Some(item);
+                break;
+            }
+            run.push(item);
+        }
+        Some(run)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        run_size_hint(self.pending.is_some(), self.iter.size_hint())
+    }
+}
+
+impl<I, F> FusedIterator for SplitWhen<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+}
+
+/// Every remaining item may start its own run, and at least one run remains
+/// while any item does.
+fn run_size_hint(
+    has_pending: bool,
+    (lower, upper): (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    let pending = usize::from(has_pending);
+    (
+        usize::from(has_pending || lower > 0),
+        upper.and_then(|upper| upper.checked_add(pending)),
+    )
+}
+
+/// Extension trait that adds the `chunk_by` and `split_when` methods to any iterator.
+///
+/// This trait mirrors [`ChunkExt`](crate::ChunkExt) for runs whose boundaries
+/// are determined by the items themselves rather than by a fixed size.
+pub trait ChunkByExt: Iterator {
+    /// Splits the iterator into runs of consecutive items that share the same key.
+    ///
+    /// See [`chunk_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ChunkByExt;
+    ///
+    /// let words = vec!["apple", "avocado", "banana", "cherry", "cranberry"];
+    /// let runs = words.into_iter().chunk_by(|w| w.chars().next());
+    /// assert_eq!(runs, vec![vec!["apple", "avocado"], vec!["banana"], vec!["cherry", "cranberry"]]);
+    /// ```
+    fn chunk_by<K>(self, key_resolver: impl FnMut(&Self::Item) -> K) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+        K: PartialEq,
+    {
+        chunk_by(self, key_resolver)
+    }
+
+    /// Lazily splits the iterator into runs of consecutive items that share the same key.
+    ///
+    /// See [`chunk_by_lazy`] for details.
+    fn chunk_by_lazy<K, F>(self, key_resolver: F) -> ChunkBy<Self, K, F>
+    where
+        Self: Sized,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        chunk_by_lazy(self, key_resolver)
+    }
+
+    /// Splits the iterator into runs wherever `should_split(previous, next)` returns `true`.
+    ///
+    /// See [`split_when`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ChunkByExt;
+    ///
+    /// let runs = vec![1, 2, 3, 2, 3].into_iter().split_when(|a, b| b < a);
+    /// assert_eq!(runs, vec![vec![1, 2, 3], vec![2, 3]]);
+    /// ```
+    fn split_when(
+        self,
+        should_split: impl FnMut(&Self::Item, &Self::Item) -> bool,
+    ) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        split_when(self, should_split)
+    }
+
+    /// Lazily splits the iterator into runs wherever `should_split(previous, next)` returns `true`.
+    ///
+    /// See [`split_when_lazy`] for details.
+    fn split_when_lazy<F>(self, should_split: F) -> SplitWhen<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        split_when_lazy(self, should_split)
+    }
+}
+
+/// Blanket implementation of `ChunkByExt` for all iterator types.
+impl<I: Iterator> ChunkByExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Event {
+        date: u32,
+        ts: u32,
+    }
+
+    fn create_events() -> Vec<Event> {
+        vec![
+            Event { date: 1, ts: 10 },
+            Event { date: 1, ts: 12 },
+            Event { date: 2, ts: 40 },
+            Event { date: 2, ts: 41 },
+            Event { date: 3, ts: 42 },
+        ]
+    }
+
+    #[test]
+    fn test_chunk_by_fn() {
+        let runs = chunk_by(create_events(), |event| event.date);
+        let dates: Vec<Vec<u32>> = runs
+            .iter()
+            .map(|run| run.iter().map(|event| event.ts).collect())
+            .collect();
+        assert_eq!(dates, vec![vec![10, 12], vec![40, 41], vec![42]]);
+    }
+
+    #[test]
+    fn test_chunk_by_ext_lazy() {
+        let mut runs = create_events()
+            .into_iter()
+            .chunk_by_lazy(|event| event.date);
+        assert_eq!(runs.next().map(|run| run.len()), Some(2));
+        assert_eq!(runs.next().map(|run| run.len()), Some(2));
+        assert_eq!(runs.next().map(|run| run.len()), Some(1));
+        assert_eq!(runs.next(), None);
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_split_when_fn() {
+        let sessions = split_when(create_events(), |a, b| b.ts - a.ts > 5);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[1][0], Event { date: 2, ts: 40 });
+    }
+
+    #[test]
+    fn test_split_when_ext_lazy() {
+        let runs: Vec<Vec<i32>> = vec![1, 2, 3, 5, 6, 9]
+            .into_iter()
+            .split_when_lazy(|a, b| b - a != 1)
+            .collect();
+        assert_eq!(runs, vec![vec![1, 2, 3], vec![5, 6], vec![9]]);
+    }
+
+    #[test]
+    fn test_chunk_by_empty() {
+        let empty: Vec<i32> = vec![];
+        assert!(chunk_by(empty.clone(), |&n| n).is_empty());
+        assert!(empty.into_iter().split_when(|_, _| true).is_empty());
+    }
+
+    #[test]
+    fn test_chunk_by_size_hint() {
+        let mut runs = vec![1, 1, 2].into_iter().chunk_by_lazy(|&n| n);
+        assert_eq!(runs.size_hint(), (1, Some(3)));
+        runs.next();
+        assert_eq!(runs.size_hint(), (1, Some(1)));
+        runs.next();
+        assert_eq!(runs.size_hint(), (0, Some(0)));
+    }
+}
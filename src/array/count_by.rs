@@ -1,5 +1,11 @@
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::ops::AddAssign;
+
+use crate::array::counter::{Counter, counter_by};
+use crate::array::sketch::{CountMinSketch, count_min_sketch};
+use crate::array::top_k::{HeavyHitter, count_by_top_k};
+use crate::collection::MapCollection;
 
 /// Counts items from an iterable collection grouped by a key derived from a resolver function.
 ///
@@ -54,6 +60,138 @@ where
     map
 }
 
+/// Counts items from an iterable collection into any map type, grouped by a key
+/// derived from a resolver function.
+///
+/// This works like [`count_by`], but the resulting map type `M` is chosen by the
+/// caller, e.g. a `BTreeMap` for sorted keys or a `HashMap` with a custom
+/// `BuildHasher`.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key
+///
+/// # Returns
+///
+/// A map of type `M` where each key corresponds to the number of items that produced it.
+///
+/// # Type Parameters
+///
+/// * `M` - The map type to collect into (must implement [`MapCollection`] with `usize` values)
+/// * `T` - The type of items in the input collection
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use rust_toolkit::count_by_into;
+///
+/// let words = vec!["banana", "apple", "blueberry", "apricot", "cherry"];
+/// let counts: BTreeMap<_, _> = count_by_into(words, |w| w.chars().next().unwrap());
+/// assert_eq!(counts, BTreeMap::from([('a', 2), ('b', 2), ('c', 1)]));
+/// ```
+pub fn count_by_into<M, T>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> M::Key,
+) -> M
+where
+    M: MapCollection<Value = usize>,
+{
+    let mut map = M::default();
+    for item in items {
+        *map.get_or_insert_with(key_resolver(&item), || 0) += 1;
+    }
+    map
+}
+
+/// Counts items from an iterable collection by a key derived from a fallible
+/// resolver function, stopping at the first error.
+///
+/// This works like [`count_by`], but `key_resolver` returns a `Result`, which is
+/// useful when keys are parsed from the items.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, usize>`, or the first error returned by `key_resolver`
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::try_count_by;
+///
+/// let codes = vec!["200", "404", "200"];
+/// let counts = try_count_by(codes, |c| c.parse::<u16>());
+/// assert_eq!(counts, Ok(HashMap::from([(200, 2), (404, 1)])));
+/// ```
+pub fn try_count_by<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, usize>, E>
+where
+    K: Hash + Eq,
+{
+    let mut map = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item)?;
+        *map.entry(key).or_insert(0) += 1;
+    }
+    Ok(map)
+}
+
+/// Like [`try_count_by`], but keeps going after an error and returns every error
+/// produced by `key_resolver`, in input order.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, usize>` if every key resolved, otherwise `Err` with all errors
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::try_count_by_collect_errors;
+///
+/// let codes = vec!["200", "oops", "4x4"];
+/// let errors = try_count_by_collect_errors(codes, |c| c.parse::<u16>()).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn try_count_by_collect_errors<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, usize>, Vec<E>>
+where
+    K: Hash + Eq,
+{
+    let mut map = HashMap::new();
+    let mut errors = Vec::new();
+    for item in items {
+        match key_resolver(&item) {
+            // Once an error has been seen the map is discarded, so stop filling it
+            Ok(key) if errors.is_empty() => {
+                *map.entry(key).or_insert(0) += 1;
+            }
+            Ok(_) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+    if errors.is_empty() {
+        Ok(map)
+    } else {
+        Err(errors)
+    }
+}
+
 /// Extension trait that adds the `count_by` method to any iterator.
 ///
 /// This trait provides a convenient way to count items by a derived key directly from an iterator.
@@ -92,14 +230,253 @@ pub trait CountByExt: Iterator {
     {
         count_by(self, key_resolver)
     }
+
+    /// Counts the iterator items into any map type by a key derived from a resolver function.
+    ///
+    /// See [`count_by_into`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let items = vec![1, 2, 3, 4, 5];
+    /// let counts = items.into_iter().count_by_into::<BTreeMap<_, _>>(|&n| n % 2);
+    /// assert_eq!(counts, BTreeMap::from([(0, 2), (1, 3)]));
+    /// ```
+    fn count_by_into<M>(self, key_resolver: impl Fn(&Self::Item) -> M::Key) -> M
+    where
+        Self: Sized,
+        M: MapCollection<Value = usize>,
+    {
+        count_by_into(self, key_resolver)
+    }
+
+    /// Like `count_by`, but with a fallible key resolver; stops at the first error.
+    ///
+    /// See [`try_count_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let counts = vec!["1", "1", "2"].into_iter().try_count_by(|s| s.parse::<u8>());
+    /// assert_eq!(counts.unwrap()[&1], 2);
+    /// ```
+    fn try_count_by<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, usize>, E>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_count_by(self, key_resolver)
+    }
+
+    /// Like `count_by`, but with a fallible key resolver; returns every error.
+    ///
+    /// See [`try_count_by_collect_errors`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let counts = vec!["1", "?"].into_iter().try_count_by_collect_errors(|s| s.parse::<u8>());
+    /// assert_eq!(counts.unwrap_err().len(), 1);
+    /// ```
+    fn try_count_by_collect_errors<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, usize>, Vec<E>>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_count_by_collect_errors(self, key_resolver)
+    }
+
+    /// Counts the iterator items into a [`Counter`] by a key derived from a resolver function.
+    ///
+    /// See [`counter_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let counts = vec![1, 2, 3, 4, 5].into_iter().counter_by(|&n| n % 2 == 0);
+    /// assert_eq!(counts.most_common(1), vec![(&false, 3)]);
+    /// ```
+    fn counter_by<K>(self, key_resolver: impl Fn(&Self::Item) -> K) -> Counter<K>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        counter_by(self, key_resolver)
+    }
+
+    /// Approximately counts the most frequent keys of the iterator using at most `k` counters.
+    ///
+    /// See [`count_by_top_k`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let top = vec![1, 1, 1, 2, 2, 3].into_iter().count_by_top_k(2, |&n| n);
+    /// assert_eq!(top[0].key, 1);
+    /// assert_eq!(top.len(), 2);
+    /// ```
+    fn count_by_top_k<K>(
+        self,
+        k: usize,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Vec<HeavyHitter<K>>
+    where
+        Self: Sized,
+        K: Hash + Eq + Clone,
+    {
+        count_by_top_k(self, k, key_resolver)
+    }
+
+    /// Builds a [`CountMinSketch`] counting the key derived from each iterator item.
+    ///
+    /// See [`count_min_sketch`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::CountByExt;
+    ///
+    /// let sketch = (0..100).count_min_sketch(64, 4, |&n| n % 2).unwrap();
+    /// assert!(sketch.estimate(&0) >= 50);
+    /// ```
+    fn count_min_sketch<K>(
+        self,
+        width: usize,
+        depth: usize,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> crate::Result<CountMinSketch>
+    where
+        Self: Sized,
+        K: Hash,
+    {
+        count_min_sketch(self, width, depth, key_resolver)
+    }
 }
 
 /// Blanket implementation of `CountByExt` for all iterator types.
 impl<I: Iterator> CountByExt for I {}
 
+/// Sums a weight derived from each item of an iterable collection, grouped by a key
+/// derived from a resolver function.
+///
+/// This is the weighted form of [`count_by`]: instead of adding one per item, it adds
+/// `weight_resolver(&item)`. The weight can be any type implementing
+/// `AddAssign + Default`, such as `u64`, `f64`, `i128` or `Duration`.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+/// * `weight_resolver` - A function that takes a reference to an item and returns its weight
+///
+/// # Returns
+///
+/// A `HashMap<K, W>` mapping each key to the total weight of the items that produced it.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys in the resulting `HashMap` (must implement `Hash + Eq`)
+/// * `W` - The weight type (must implement `AddAssign + Default`)
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+/// use rust_toolkit::sum_by_key;
+///
+/// let requests = vec![("/a", 120), ("/b", 40), ("/a", 30)];
+/// let latency = sum_by_key(requests, |&(path, _)| path, |&(_, ms)| Duration::from_millis(ms));
+/// assert_eq!(latency[&"/a"], Duration::from_millis(150));
+///
+/// let totals = sum_by_key(vec![("x", 1.5), ("x", 2.0)], |&(k, _)| k, |&(_, v)| v);
+/// assert_eq!(totals, HashMap::from([("x", 3.5)]));
+/// ```
+pub fn sum_by_key<T, K, W>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+    weight_resolver: impl Fn(&T) -> W,
+) -> HashMap<K, W>
+where
+    K: Hash + Eq,
+    W: AddAssign + Default,
+{
+    let mut map: HashMap<K, W> = HashMap::new();
+    for item in items {
+        // Derive the key for this item and add its weight to the corresponding total
+        let weight = weight_resolver(&item);
+        *map.entry(key_resolver(&item)).or_default() += weight;
+    }
+    map
+}
+
+/// Extension trait that adds the `sum_by_key` method to any iterator.
+///
+/// This trait provides a convenient way to total a weight per derived key directly from an iterator.
+pub trait SumByKeyExt: Iterator {
+    /// Sums a weight derived from each iterator item, grouped by a key derived from a
+    /// resolver function.
+    ///
+    /// See [`sum_by_key`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_resolver` - A function that takes a reference to an item and returns a key
+    /// * `weight_resolver` - A function that takes a reference to an item and returns its weight
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` mapping each key to the total weight of the items that produced it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SumByKeyExt;
+    ///
+    /// let orders = vec![("alice", 10u64), ("bob", 5), ("alice", 7)];
+    /// let totals = orders.into_iter().sum_by_key(|&(name, _)| name, |&(_, amount)| amount);
+    /// assert_eq!(totals[&"alice"], 17);
+    /// ```
+    fn sum_by_key<K, W>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+        weight_resolver: impl Fn(&Self::Item) -> W,
+    ) -> HashMap<K, W>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+        W: AddAssign + Default,
+    {
+        sum_by_key(self, key_resolver, weight_resolver)
+    }
+}
+
+/// Blanket implementation of `SumByKeyExt` for all iterator types.
+impl<I: Iterator> SumByKeyExt for I {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
+    use std::hash::{BuildHasherDefault, DefaultHasher};
 
     #[test]
     fn test_count_by_fn() {
@@ -116,4 +493,72 @@ mod tests {
             .count_by(|&item| if item % 2 == 0 { "even" } else { "odd" });
         assert_eq!(result, HashMap::from([("odd", 3), ("even", 2)]));
     }
+
+    #[test]
+    fn test_count_by_into_btree_map() {
+        let items = vec!["b", "a", "c", "a"];
+        let result: BTreeMap<_, _> = count_by_into(items, |&s| s);
+        assert_eq!(
+            result.into_iter().collect::<Vec<_>>(),
+            vec![("a", 2), ("b", 1), ("c", 1)]
+        );
+    }
+
+    #[test]
+    fn test_count_by_into_custom_hasher() {
+        let items = vec![1, 2, 3, 4, 5];
+        let result = items
+            .into_iter()
+            .count_by_into::<HashMap<_, _, BuildHasherDefault<DefaultHasher>>>(|&n| n > 2);
+        assert_eq!(result[&true], 3);
+        assert_eq!(result[&false], 2);
+    }
+
+    #[test]
+    fn test_try_count_by_fn() {
+        let items = vec!["1", "2", "x", "y"];
+        let result = try_count_by(items, |s| s.parse::<u8>().map_err(|_| *s));
+        assert_eq!(result, Err("x"));
+    }
+
+    #[test]
+    fn test_try_count_by_ext() {
+        let items = vec!["1", "2", "1"];
+        let result = items.into_iter().try_count_by(|s| s.parse::<u8>());
+        assert_eq!(result, Ok(HashMap::from([(1, 2), (2, 1)])));
+    }
+
+    #[test]
+    fn test_try_count_by_collect_errors() {
+        let items = vec!["1", "2", "x", "y"];
+        let result = items
+            .into_iter()
+            .try_count_by_collect_errors(|s| s.parse::<u8>().map_err(|_| *s));
+        assert_eq!(result, Err(vec!["x", "y"]));
+    }
+
+    #[test]
+    fn test_sum_by_key_fn() {
+        let items = vec![("a", 2u64), ("b", 3), ("a", 4)];
+        let result = sum_by_key(items, |&(k, _)| k, |&(_, w)| w);
+        assert_eq!(result, HashMap::from([("a", 6), ("b", 3)]));
+    }
+
+    #[test]
+    fn test_sum_by_key_ext_signed_and_float() {
+        let deltas = vec![(1, -5i128), (2, 10), (1, 3)];
+        let result = deltas.into_iter().sum_by_key(|&(k, _)| k, |&(_, d)| d);
+        assert_eq!(result, HashMap::from([(1, -2), (2, 10)]));
+
+        let readings = vec![0.5f64, 1.5, 2.0];
+        let result = readings.into_iter().sum_by_key(|&r| r > 1.0, |&r| r);
+        assert_eq!(result, HashMap::from([(false, 0.5), (true, 3.5)]));
+    }
+
+    #[test]
+    fn test_sum_by_key_with_unit_weight_matches_count_by() {
+        let items = vec![1, 2, 3, 4, 5];
+        let sums = sum_by_key(items.clone(), |&n| n % 2, |_| 1usize);
+        assert_eq!(sums, count_by(items, |&n| n % 2));
+    }
 }
@@ -0,0 +1,387 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::collections::hash_map;
+use std::hash::Hash;
+use std::ops::{Add, BitAnd, BitOr, Sub};
+
+use crate::collection::MapCollection;
+
+/// A multiset that counts how many times each key was seen, similar to Python's
+/// `collections.Counter`.
+///
+/// A `Counter` is produced by [`counter_by`], [`CountByExt::counter_by`](crate::CountByExt::counter_by)
+/// or [`count_by_into`](crate::count_by_into), and can also be collected directly
+/// from an iterator of keys. Missing keys have a count of 0, and keys whose count
+/// drops to 0 are removed.
+///
+/// Counters can be combined with `+` (sum of counts, saturating at `usize::MAX`),
+/// `-` (difference, dropping keys that fall to 0), `&` (minimum of counts) and `|`
+/// (maximum of counts).
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::Counter;
+///
+/// let mut words: Counter<&str> = "the cat and the hat".split(' ').collect();
+/// assert_eq!(words.get(&"the"), 2);
+/// assert_eq!(words.get(&"dog"), 0);
+/// assert_eq!(words.total(), 5);
+///
+/// words.update(["cat", "cat"]);
+/// assert_eq!(words.most_common(1), vec![(&"cat", 3)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Counter<K> {
+    counts: HashMap<K, usize>,
+}
+
+impl<K> Counter<K> {
+    /// Creates an empty counter.
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns `true` if no keys have been counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the sum of all counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns up to `n` keys with the highest counts, most common first.
+    ///
+    /// Keys with equal counts are returned in unspecified order, so when the cut-off
+    /// at `n` falls within a tie, which of the tied keys are included is unspecified
+    /// as well.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, usize)> {
+        let mut entries = self.entries();
+        entries.sort_by_key(|&(_, count)| Reverse(count));
+        entries.truncate(n);
+        entries
+    }
+
+    /// Returns up to `n` keys with the lowest counts, least common first.
+    ///
+    /// Keys with equal counts are returned in unspecified order, so when the cut-off
+    /// at `n` falls within a tie, which of the tied keys are included is unspecified
+    /// as well.
+    pub fn least_common(&self, n: usize) -> Vec<(&K, usize)> {
+        let mut entries = self.entries();
+        entries.sort_by_key(|&(_, count)| count);
+        entries.truncate(n);
+        entries
+    }
+
+    /// Returns an iterator over the keys and their counts in no particular order.
+    pub fn iter(&self) -> hash_map::Iter<'_, K, usize> {
+        self.counts.iter()
+    }
+
+    /// Returns an iterator over the counted keys in no particular order.
+    pub fn keys(&self) -> hash_map::Keys<'_, K, usize> {
+        self.counts.keys()
+    }
+
+    fn entries(&self) -> Vec<(&K, usize)> {
+        self.counts
+            .iter()
+            .map(|(key, &count)| (key, count))
+            .collect()
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    /// Returns the count of `key`, or 0 if it was never seen.
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Increments the count of `key` by `count`.
+    ///
+    /// Adding a count of 0 leaves the counter unchanged.
+    pub fn increment_by(&mut self, key: K, count: usize) {
+        if count > 0 {
+            *self.counts.entry(key).or_insert(0) += count;
+        }
+    }
+
+    /// Increments the count of every key produced by `keys` by one.
+    pub fn update(&mut self, keys: impl IntoIterator<Item = K>) {
+        for key in keys {
+            self.increment_by(key, 1);
+        }
+    }
+
+    /// Removes `key`, returning its previous count.
+    pub fn remove(&mut self, key: &K) -> usize {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// Keeps the keys of both counters, combining their counts with `combine` and
+    /// dropping keys whose combined count is 0.
+    fn merge_with(mut self, mut other: Self, combine: impl Fn(usize, usize) -> usize) -> Self {
+        let mut counts = HashMap::with_capacity(self.counts.len().max(other.counts.len()));
+        for (key, count) in self.counts.drain() {
+            let other_count = other.counts.remove(&key).unwrap_or(0);
+            counts.insert(key, combine(count, other_count));
+        }
+        for (key, other_count) in other.counts {
+            counts.insert(key, combine(0, other_count));
+        }
+        counts.retain(|_, count| *count > 0);
+        Counter { counts }
+    }
+}
+
+impl<K: Hash + Eq> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Hash + Eq> Eq for Counter<K> {}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut counter = Counter::new();
+        counter.update(keys);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, keys: I) {
+        self.update(keys);
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<'a, K> IntoIterator for &'a Counter<K> {
+    type Item = (&'a K, &'a usize);
+    type IntoIter = hash_map::Iter<'a, K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter()
+    }
+}
+
+impl<K: Hash + Eq> From<HashMap<K, usize>> for Counter<K> {
+    fn from(mut counts: HashMap<K, usize>) -> Self {
+        counts.retain(|_, count| *count > 0);
+        Counter { counts }
+    }
+}
+
+impl<K> From<Counter<K>> for HashMap<K, usize> {
+    fn from(counter: Counter<K>) -> Self {
+        counter.counts
+    }
+}
+
+/// Lets the `*_into` functions, such as [`count_by_into`](crate::count_by_into),
+/// produce a `Counter`.
+///
+/// Inserting a count of 0 removes the key, so the counter never holds zero counts.
+/// The count returned by `get_or_insert_with` is expected to be incremented by the
+/// caller, as `count_by_into` does.
+impl<K: Hash + Eq> MapCollection for Counter<K> {
+    type Key = K;
+    type Value = usize;
+
+    fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> usize) -> &mut usize {
+        self.counts.entry(key).or_insert_with(default)
+    }
+
+    fn insert(&mut self, key: K, value: usize) -> Option<usize> {
+        if value == 0 {
+            return self.counts.remove(&key);
+        }
+        self.counts.insert(key, value)
+    }
+}
+
+/// Adds the counts of both counters, saturating at `usize::MAX` instead of
+/// overflowing.
+impl<K: Hash + Eq> Add for Counter<K> {
+    type Output = Counter<K>;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.merge_with(other, usize::saturating_add)
+    }
+}
+
+/// Subtracts the counts of `other`, keeping only keys whose count stays positive.
+impl<K: Hash + Eq> Sub for Counter<K> {
+    type Output = Counter<K>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.merge_with(other, usize::saturating_sub)
+    }
+}
+
+/// Intersection: keeps the minimum count of every key present in both counters.
+impl<K: Hash + Eq> BitAnd for Counter<K> {
+    type Output = Counter<K>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.merge_with(other, usize::min)
+    }
+}
+
+/// Union: keeps the maximum count of every key present in either counter.
+impl<K: Hash + Eq> BitOr for Counter<K> {
+    type Output = Counter<K>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.merge_with(other, usize::max)
+    }
+}
+
+/// Counts items from an iterable collection into a [`Counter`], grouped by a key
+/// derived from a resolver function.
+///
+/// This works like [`count_by`](crate::count_by), but returns a [`Counter`] that
+/// offers `most_common`, `total` and counter arithmetic.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// A [`Counter<K>`] holding the number of items that produced each key.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::counter_by;
+///
+/// let words = vec!["apple", "banana", "avocado", "apricot", "blueberry"];
+/// let by_letter = counter_by(words, |w| w.chars().next().unwrap());
+/// assert_eq!(by_letter.most_common(1), vec![(&'a', 3)]);
+/// assert_eq!(by_letter.total(), 5);
+/// ```
+pub fn counter_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Counter<K>
+where
+    K: Hash + Eq,
+{
+    items.into_iter().map(|item| key_resolver(&item)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CountByExt, count_by, count_by_into};
+
+    fn counter(pairs: &[(&'static str, usize)]) -> Counter<&'static str> {
+        let mut counter = Counter::new();
+        for &(key, count) in pairs {
+            counter.increment_by(key, count);
+        }
+        counter
+    }
+
+    #[test]
+    fn test_counter_by_matches_count_by() {
+        let items = vec![1, 2, 3, 4, 5, 6, 7];
+        let counter = counter_by(items.clone(), |&n| n % 3);
+        assert_eq!(HashMap::from(counter), count_by(items, |&n| n % 3));
+    }
+
+    #[test]
+    fn test_counter_by_ext_and_count_by_into() {
+        let words = vec!["a", "b", "a"];
+        let from_ext = words.clone().into_iter().counter_by(|&w| w);
+        let from_into: Counter<_> = count_by_into(words, |&w| w);
+        assert_eq!(from_ext, from_into);
+        assert_eq!(from_ext.get(&"a"), 2);
+    }
+
+    #[test]
+    fn test_most_and_least_common() {
+        let counter = counter(&[("a", 5), ("b", 1), ("c", 3)]);
+        assert_eq!(counter.most_common(2), vec![(&"a", 5), (&"c", 3)]);
+        assert_eq!(counter.least_common(1), vec![(&"b", 1)]);
+        assert_eq!(counter.most_common(10).len(), 3);
+        assert_eq!(counter.total(), 9);
+    }
+
+    #[test]
+    fn test_counter_from_iter_and_extend() {
+        let mut counter: Counter<char> = "hello".chars().collect();
+        assert_eq!(counter.get(&'l'), 2);
+        counter.extend("world".chars());
+        assert_eq!(counter.get(&'l'), 3);
+        assert_eq!(counter.get(&'o'), 2);
+        assert_eq!(counter.len(), 7);
+        assert_eq!(counter.remove(&'l'), 3);
+        assert_eq!(counter.get(&'l'), 0);
+    }
+
+    #[test]
+    fn test_counter_arithmetic() {
+        let a = counter(&[("x", 3), ("y", 1)]);
+        let b = counter(&[("x", 1), ("y", 2), ("z", 4)]);
+
+        assert_eq!(
+            a.clone() + b.clone(),
+            counter(&[("x", 4), ("y", 3), ("z", 4)])
+        );
+        assert_eq!(a.clone() - b.clone(), counter(&[("x", 2)]));
+        assert_eq!(a.clone() & b.clone(), counter(&[("x", 1), ("y", 1)]));
+        assert_eq!(a | b, counter(&[("x", 3), ("y", 2), ("z", 4)]));
+
+        let huge = counter(&[("x", usize::MAX)]);
+        assert_eq!(huge.clone() + counter(&[("x", 1)]), huge);
+    }
+
+    #[test]
+    fn test_counter_from_hash_map_drops_zero_counts() {
+        let counter = Counter::from(HashMap::from([("a", 0), ("b", 2)]));
+        assert_eq!(counter.len(), 1);
+        assert!(!counter.is_empty());
+    }
+
+    #[test]
+    fn test_inserting_zero_removes_key() {
+        let mut counter = Counter::new();
+        for (key, count) in [("a", 0), ("b", 2), ("c", 0)] {
+            MapCollection::insert(&mut counter, key, count);
+        }
+        assert_eq!(counter.len(), 1);
+        assert_eq!(counter.total(), 2);
+
+        let mut counter = Counter::from(HashMap::from([("a", 3)]));
+        assert_eq!(MapCollection::insert(&mut counter, "a", 0), Some(3));
+        assert!(counter.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 
+use crate::collection::MapCollection;
+
 /// Groups items from an iterable collection into a HashMap based on a key resolver function.
 ///
 /// This function takes any iterable collection and groups the items by keys derived
@@ -55,6 +57,480 @@ where
     map
 }
 
+/// Groups items from an iterable collection by a key, storing a projected value for
+/// each item instead of the item itself.
+///
+/// This is equivalent to grouping with [`group_by`] and then mapping every group
+/// through `value_resolver`, but avoids materialising the intermediate `Vec<T>`s.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+/// * `value_resolver` - A function that consumes an item and returns the value to store
+///
+/// # Returns
+///
+/// A HashMap where keys are of type `K` and values are vectors of projected values
+/// of type `V`, in input order.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys in the resulting HashMap (must implement Hash + Eq)
+/// * `V` - The type of values stored in each group
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::group_by_map;
+///
+/// let orders = vec![("alice", 10), ("bob", 5), ("alice", 7)];
+/// let amounts = group_by_map(orders, |&(name, _)| name, |(_, amount)| amount);
+/// assert_eq!(amounts, HashMap::from([("alice", vec![10, 7]), ("bob", vec![5])]));
+/// ```
+pub fn group_by_map<T, K, V>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+    value_resolver: impl Fn(T) -> V,
+) -> HashMap<K, Vec<V>>
+where
+    K: Hash + Eq,
+{
+    let mut map: HashMap<K, Vec<V>> = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item);
+        map.entry(key).or_default().push(value_resolver(item));
+    }
+    map
+}
+
+/// Groups items from an iterable collection by a key and folds each group into a
+/// single accumulated value, like an SQL `GROUP BY` with an aggregate.
+///
+/// Each group's accumulator starts as a clone of `init`, and `fold` is called with
+/// the group's accumulator and every item that belongs to it, in input order. No
+/// per-group `Vec` is allocated.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+/// * `init` - The initial accumulator value of every group
+/// * `fold` - A function that updates a group's accumulator with one of its items
+///
+/// # Returns
+///
+/// A HashMap from each key to the final accumulator of its group
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys in the resulting HashMap (must implement Hash + Eq)
+/// * `A` - The accumulator type (must implement Clone)
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::group_by_fold;
+///
+/// let orders = vec![("alice", 10), ("bob", 5), ("alice", 7)];
+/// let totals = group_by_fold(orders, |&(name, _)| name, 0, |total, (_, amount)| *total += amount);
+/// assert_eq!(totals, HashMap::from([("alice", 17), ("bob", 5)]));
+/// ```
+pub fn group_by_fold<T, K, A>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+    init: A,
+    fold: impl Fn(&mut A, T),
+) -> HashMap<K, A>
+where
+    K: Hash + Eq,
+    A: Clone,
+{
+    let mut map: HashMap<K, A> = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item);
+        let accumulator = map.entry(key).or_insert_with(|| init.clone());
+        fold(accumulator, item);
+    }
+    map
+}
+
+/// Groups items from an iterable collection into any map and group container type.
+///
+/// This works like [`group_by`], but the resulting map type `M` and the group type
+/// stored for each key are chosen by the caller. `M` can be a `BTreeMap` for sorted
+/// keys or a `HashMap` with a custom `BuildHasher`, and each group can be any
+/// collection implementing `Default + Extend<T>` such as `Vec`, `VecDeque`,
+/// `HashSet` or `BTreeSet`.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key
+///
+/// # Returns
+///
+/// A map of type `M` from each key to the group of items that share it
+///
+/// # Type Parameters
+///
+/// * `M` - The map type to collect into (must implement [`MapCollection`])
+/// * `T` - The type of items in the input collection
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::{BTreeMap, BTreeSet};
+/// use rust_toolkit::group_by_into;
+///
+/// let numbers = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// let grouped: BTreeMap<_, BTreeSet<_>> = group_by_into(numbers, |&n| n % 3);
+/// assert_eq!(
+///     grouped,
+///     BTreeMap::from([
+///         (0, BTreeSet::from([3, 6, 9])),
+///         (1, BTreeSet::from([1, 4])),
+///         (2, BTreeSet::from([2, 5])),
+///     ])
+/// );
+/// ```
+pub fn group_by_into<M, T>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> M::Key,
+) -> M
+where
+    M: MapCollection,
+    M::Value: Default + Extend<T>,
+{
+    let mut map = M::default();
+    for item in items {
+        let key = key_resolver(&item);
+        map.get_or_insert_with(key, Default::default)
+            .extend(std::iter::once(item));
+    }
+    map
+}
+
+/// Groups items from an iterable collection by a key resolver function, keeping the
+/// groups in the order their keys were first seen.
+///
+/// This works like [`group_by`], but returns a `Vec` of `(key, items)` pairs instead
+/// of a `HashMap`, so iterating over the result is deterministic. Items within each
+/// group keep their input order.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// A `Vec<(K, Vec<T>)>` with one entry per distinct key, in first-seen key order
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys (must implement Hash + Eq + Clone)
+/// * `F` - The type of the key resolver function
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::group_by_ordered;
+///
+/// let words = vec!["banana", "apple", "blueberry", "apricot", "cherry"];
+/// let groups = group_by_ordered(words, |word| word.chars().next().unwrap());
+/// assert_eq!(
+///     groups,
+///     vec![
+///         ('b', vec!["banana", "blueberry"]),
+///         ('a', vec!["apple", "apricot"]),
+///         ('c', vec!["cherry"]),
+///     ]
+/// );
+/// ```
+pub fn group_by_ordered<T, K, F>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: F,
+) -> Vec<(K, Vec<T>)>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&T) -> K,
+{
+    // Maps each key to the position of its group in `groups`
+    let mut positions: HashMap<K, usize> = HashMap::new();
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    for item in items {
+        let key = key_resolver(&item);
+        match positions.get(&key) {
+            Some(&position) => groups[position].1.push(item),
+            None => {
+                positions.insert(key.clone(), groups.len());
+                groups.push((key, vec![item]));
+            }
+        }
+    }
+    groups
+}
+
+/// Groups items from an iterable collection into nested HashMaps, one level per key
+/// resolver.
+///
+/// `key_resolvers` is a tuple of one to four key resolver functions. The first
+/// resolver decides the outermost key, the second the key inside that group, and
+/// so on; the innermost level holds the items in input order. For example two
+/// resolvers produce a `HashMap<K1, HashMap<K2, Vec<T>>>`.
+///
+/// Because the resolvers are passed as a tuple, closure parameters need a type
+/// annotation (e.g. `|sale: &Sale| sale.region`).
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolvers` - A tuple of key resolver functions, outermost first
+///
+/// # Returns
+///
+/// The nested map described by [`NestedKeyResolvers::Output`]
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::group_by_nested;
+///
+/// struct Sale { region: &'static str, country: &'static str, amount: u32 }
+///
+/// let sales = vec![
+///     Sale { region: "EU", country: "FR", amount: 10 },
+///     Sale { region: "EU", country: "DE", amount: 20 },
+///     Sale { region: "EU", country: "FR", amount: 30 },
+///     Sale { region: "NA", country: "US", amount: 40 },
+/// ];
+/// let tree = group_by_nested(sales, (|s: &Sale| s.region, |s: &Sale| s.country));
+/// let french: Vec<u32> = tree["EU"]["FR"].iter().map(|s| s.amount).collect();
+/// assert_eq!(french, vec![10, 30]);
+/// assert_eq!(tree["NA"].len(), 1);
+/// ```
+pub fn group_by_nested<T, R>(items: impl IntoIterator<Item = T>, key_resolvers: R) -> R::Output
+where
+    R: NestedKeyResolvers<T>,
+{
+    let mut map = R::Output::default();
+    for item in items {
+        key_resolvers.insert(&mut map, item);
+    }
+    map
+}
+
+/// Flattens the nested HashMaps produced by [`group_by_nested`] back into rows of
+/// `(K1, ..., Kn, T)`.
+///
+/// Rows of the same group keep their input order, but the order of the groups
+/// themselves follows HashMap iteration order.
+///
+/// # Arguments
+///
+/// * `groups` - A nested map of one to four levels ending in `Vec<T>`
+///
+/// # Returns
+///
+/// A `Vec` of flat tuples holding every key on the path to an item followed by the item
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{flatten_groups, group_by_nested};
+///
+/// let numbers = vec![1, 2, 3, 4, 5, 6];
+/// let tree = group_by_nested(numbers, (|n: &i32| n % 2, |n: &i32| *n > 3));
+/// let mut rows = flatten_groups(tree);
+/// rows.sort();
+/// assert_eq!(
+///     rows,
+///     vec![(0, false, 2), (0, true, 4), (0, true, 6), (1, false, 1), (1, false, 3), (1, true, 5)]
+/// );
+/// ```
+pub fn flatten_groups<G: FlattenGroups>(groups: G) -> Vec<G::Row> {
+    groups.flatten_groups()
+}
+
+/// A tuple of key resolver functions accepted by [`group_by_nested`].
+///
+/// This trait is implemented for tuples of one to four `Fn(&T) -> K` functions
+/// whose keys implement `Hash + Eq`.
+pub trait NestedKeyResolvers<T> {
+    /// The nested map type produced by grouping with these resolvers.
+    type Output: Default;
+
+    /// Inserts `item` into `map` under the keys resolved for it.
+    fn insert(&self, map: &mut Self::Output, item: T);
+}
+
+/// A nested map of groups that can be flattened back into rows.
+///
+/// This trait is implemented for the one to four level maps produced by
+/// [`group_by_nested`], as long as every key implements `Clone`.
+pub trait FlattenGroups {
+    /// The flat `(K1, ..., Kn, T)` tuple produced for each item.
+    type Row;
+
+    /// Flattens the nested groups into rows.
+    fn flatten_groups(self) -> Vec<Self::Row>;
+}
+
+/// Expands to the nested map type for the given key types, outermost first.
+macro_rules! nested_map {
+    ($item:ty;) => { Vec<$item> };
+    ($item:ty; $key:ident $(, $rest:ident)*) => { HashMap<$key, nested_map!($item; $($rest),*)> };
+}
+
+/// Expands to nested loops that push one row per item, cloning the keys on the path.
+macro_rules! push_rows {
+    ($rows:ident, $groups:expr, ($($path:ident),*);) => {
+        for item in $groups {
+            $rows.push(($($path.clone(),)* item));
+        }
+    };
+    ($rows:ident, $groups:expr, ($($path:ident),*); $key:ident $(, $rest:ident)*) => {
+        for ($key, inner) in $groups {
+            push_rows!($rows, inner, ($($path,)* $key); $($rest),*);
+        }
+    };
+}
+
+macro_rules! impl_nested_group_by {
+    ($(($resolver:ident, $key:ident, $index:tt, $binding:ident)),+) => {
+        impl<T, $($resolver, $key),+> NestedKeyResolvers<T> for ($($resolver,)+)
+        where
+            $($resolver: Fn(&T) -> $key, $key: Hash + Eq,)+
+        {
+            type Output = nested_map!(T; $($key),+);
+
+            fn insert(&self, map: &mut Self::Output, item: T) {
+                map$(.entry((self.$index)(&item)).or_default())+.push(item);
+            }
+        }
+
+        impl<T, $($key),+> FlattenGroups for nested_map!(T; $($key),+)
+        where
+            $($key: Hash + Eq + Clone,)+
+        {
+            type Row = ($($key,)+ T);
+
+            fn flatten_groups(self) -> Vec<Self::Row> {
+                let mut rows = Vec::new();
+                push_rows!(rows, self, (); $($binding),+);
+                rows
+            }
+        }
+    };
+}
+
+impl_nested_group_by!((F1, K1, 0, k1));
+impl_nested_group_by!((F1, K1, 0, k1), (F2, K2, 1, k2));
+impl_nested_group_by!((F1, K1, 0, k1), (F2, K2, 1, k2), (F3, K3, 2, k3));
+impl_nested_group_by!(
+    (F1, K1, 0, k1),
+    (F2, K2, 1, k2),
+    (F3, K3, 2, k3),
+    (F4, K4, 3, k4)
+);
+
+/// Groups items from an iterable collection into a HashMap using a fallible key
+/// resolver function, stopping at the first error.
+///
+/// This works like [`group_by`], but `key_resolver` returns a `Result`, which is
+/// useful when keys are parsed from the items.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, Vec<T>>`, or the first error returned by `key_resolver`
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::try_group_by;
+///
+/// let lines = vec!["1:a", "2:b", "1:c"];
+/// let grouped = try_group_by(lines.clone(), |line| line[..1].parse::<u32>());
+/// assert_eq!(grouped, Ok(HashMap::from([(1, vec!["1:a", "1:c"]), (2, vec!["2:b"])])));
+///
+/// let grouped = try_group_by(vec!["1:a", "x:b"], |line| line[..1].parse::<u32>());
+/// assert!(grouped.is_err());
+/// ```
+pub fn try_group_by<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, Vec<T>>, E>
+where
+    K: Hash + Eq,
+{
+    let mut map: HashMap<K, Vec<T>> = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item)?;
+        map.entry(key).or_default().push(item);
+    }
+    Ok(map)
+}
+
+/// Like [`try_group_by`], but keeps going after an error and returns every error
+/// produced by `key_resolver`, in input order.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, Vec<T>>` if every key resolved, otherwise `Err` with all errors
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::try_group_by_collect_errors;
+///
+/// let lines = vec!["1:a", "x:b", "y:c"];
+/// let errors = try_group_by_collect_errors(lines, |line| line[..1].parse::<u32>()).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn try_group_by_collect_errors<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, Vec<T>>, Vec<E>>
+where
+    K: Hash + Eq,
+{
+    let mut map: HashMap<K, Vec<T>> = HashMap::new();
+    let mut errors = Vec::new();
+    for item in items {
+        match key_resolver(&item) {
+            // Once an error has been seen the map is discarded, so stop filling it
+            Ok(key) if errors.is_empty() => {
+                map.entry(key).or_default().push(item);
+            }
+            Ok(_) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+    if errors.is_empty() {
+        Ok(map)
+    } else {
+        Err(errors)
+    }
+}
+
 /// Extension trait that adds the `group_by` method to any iterator.
 ///
 /// This trait provides a convenient way to group iterator items into HashMaps
@@ -104,6 +580,204 @@ pub trait GroupByExt: Iterator {
     {
         group_by(self, key_resolver)
     }
+
+    /// Groups the iterator items by a key, storing a projected value for each item.
+    ///
+    /// See [`group_by_map`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let words = vec!["apple", "avocado", "banana"];
+    /// let lengths = words
+    ///     .into_iter()
+    ///     .group_by_map(|w| w.chars().next().unwrap(), |w| w.len());
+    /// assert_eq!(lengths, HashMap::from([('a', vec![5, 7]), ('b', vec![6])]));
+    /// ```
+    fn group_by_map<K, V>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+        value_resolver: impl Fn(Self::Item) -> V,
+    ) -> HashMap<K, Vec<V>>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        group_by_map(self, key_resolver, value_resolver)
+    }
+
+    /// Groups the iterator items by a key and folds each group into a single value.
+    ///
+    /// See [`group_by_fold`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let readings = vec![("a", 3), ("b", 9), ("a", 1), ("b", 4)];
+    /// let minimums = readings.into_iter().group_by_fold(
+    ///     |&(sensor, _)| sensor,
+    ///     i32::MAX,
+    ///     |min, (_, value)| *min = (*min).min(value),
+    /// );
+    /// assert_eq!(minimums, HashMap::from([("a", 1), ("b", 4)]));
+    /// ```
+    fn group_by_fold<K, A>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+        init: A,
+        fold: impl Fn(&mut A, Self::Item),
+    ) -> HashMap<K, A>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+        A: Clone,
+    {
+        group_by_fold(self, key_resolver, init, fold)
+    }
+
+    /// Groups the iterator items into any map and group container type.
+    ///
+    /// See [`group_by_into`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_resolver` - A function that takes a reference to an item and returns a key
+    ///
+    /// # Returns
+    ///
+    /// A map of type `M` from each key to the group of items that share it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::{BTreeMap, VecDeque};
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let words = vec!["banana", "apple", "blueberry"];
+    /// let grouped = words
+    ///     .into_iter()
+    ///     .group_by_into::<BTreeMap<_, VecDeque<_>>>(|word| word.chars().next().unwrap());
+    /// assert_eq!(grouped.keys().collect::<Vec<_>>(), vec![&'a', &'b']);
+    /// ```
+    fn group_by_into<M>(self, key_resolver: impl Fn(&Self::Item) -> M::Key) -> M
+    where
+        Self: Sized,
+        M: MapCollection,
+        M::Value: Default + Extend<Self::Item>,
+    {
+        group_by_into(self, key_resolver)
+    }
+
+    /// Groups the iterator items by a key resolver function, keeping the groups in
+    /// the order their keys were first seen.
+    ///
+    /// See [`group_by_ordered`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_resolver` - A function that takes a reference to an item and returns a key
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(K, Vec<Self::Item>)>` in first-seen key order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let numbers = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    /// let groups = numbers.into_iter().group_by_ordered(|&n| n % 3);
+    /// assert_eq!(groups, vec![(0, vec![3, 9, 6]), (1, vec![1, 4, 1]), (2, vec![5, 2])]);
+    /// ```
+    fn group_by_ordered<K, F>(self, key_resolver: F) -> Vec<(K, Vec<Self::Item>)>
+    where
+        Self: Sized,
+        K: Hash + Eq + Clone,
+        F: Fn(&Self::Item) -> K,
+    {
+        group_by_ordered(self, key_resolver)
+    }
+
+    /// Groups the iterator items into nested HashMaps, one level per key resolver.
+    ///
+    /// See [`group_by_nested`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let words = vec!["apple", "avocado", "banana", "apricot"];
+    /// let tree = words
+    ///     .into_iter()
+    ///     .group_by_nested((|w: &&str| w.chars().next().unwrap(), |w: &&str| w.len()));
+    /// assert_eq!(tree[&'a'][&7], vec!["avocado", "apricot"]);
+    /// ```
+    fn group_by_nested<R>(self, key_resolvers: R) -> R::Output
+    where
+        Self: Sized,
+        R: NestedKeyResolvers<Self::Item>,
+    {
+        group_by_nested(self, key_resolvers)
+    }
+
+    /// Like `group_by`, but with a fallible key resolver; stops at the first error.
+    ///
+    /// See [`try_group_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let grouped = vec!["a1", "b2", "c1"]
+    ///     .into_iter()
+    ///     .try_group_by(|s| s[1..].parse::<u8>());
+    /// assert_eq!(grouped.unwrap()[&1], vec!["a1", "c1"]);
+    /// ```
+    fn try_group_by<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, Vec<Self::Item>>, E>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_group_by(self, key_resolver)
+    }
+
+    /// Like `group_by`, but with a fallible key resolver; returns every error.
+    ///
+    /// See [`try_group_by_collect_errors`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::GroupByExt;
+    ///
+    /// let errors = vec!["a1", "bx", "cy"]
+    ///     .into_iter()
+    ///     .try_group_by_collect_errors(|s| s[1..].parse::<u8>())
+    ///     .unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    fn try_group_by_collect_errors<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, Vec<Self::Item>>, Vec<E>>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_group_by_collect_errors(self, key_resolver)
+    }
 }
 
 /// Blanket implementation of GroupByExt for all iterator types.
@@ -115,6 +789,30 @@ impl<I: Iterator> GroupByExt for I {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// A minimal FNV-1a hasher to exercise custom `BuildHasher`s.
+    #[derive(Default)]
+    struct FnvHasher(u64);
+
+    impl Hasher for FnvHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            if self.0 == 0 {
+                self.0 = 0xcbf29ce484222325;
+            }
+            for &byte in bytes {
+                self.0 ^= u64::from(byte);
+                self.0 = self.0.wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+
+    type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
 
     #[derive(Debug, PartialEq, Clone)]
     struct User {
@@ -276,4 +974,214 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_group_by_ordered_fn() {
+        let users = create_users();
+        let user_group = group_by_ordered(users, |user| user.age);
+
+        let ids: Vec<(u32, Vec<u32>)> = user_group
+            .into_iter()
+            .map(|(age, users)| (age, users.into_iter().map(|user| user.id).collect()))
+            .collect();
+        assert_eq!(ids, vec![(20, vec![1, 2]), (30, vec![3])]);
+    }
+
+    #[test]
+    fn test_group_by_ordered_ext_keeps_first_seen_order() {
+        let words = vec!["cherry", "apple", "cranberry", "banana", "apricot"];
+        let grouped = words
+            .into_iter()
+            .group_by_ordered(|word| word.chars().next().unwrap());
+
+        assert_eq!(
+            grouped,
+            vec![
+                ('c', vec!["cherry", "cranberry"]),
+                ('a', vec!["apple", "apricot"]),
+                ('b', vec!["banana"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_ordered_empty_collection() {
+        let empty_vec: Vec<i32> = vec![];
+        assert!(group_by_ordered(empty_vec, |&x| x).is_empty());
+    }
+
+    #[test]
+    fn test_group_by_into_btree_map_of_sets() {
+        let items = vec![1, 2, 3, 4, 5, 6, 1, 2];
+        let grouped: BTreeMap<_, BTreeSet<_>> = group_by_into(items, |&n| n % 2);
+        assert_eq!(
+            grouped,
+            BTreeMap::from([
+                (0, BTreeSet::from([2, 4, 6])),
+                (1, BTreeSet::from([1, 3, 5]))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_group_by_into_custom_hasher() {
+        let users = create_users();
+        let grouped = users
+            .into_iter()
+            .group_by_into::<HashMap<_, VecDeque<_>, FnvBuildHasher>>(|user| user.age);
+        assert_eq!(grouped[&20].len(), 2);
+        assert_eq!(grouped[&30].front().map(|user| user.id), Some(3));
+    }
+
+    #[test]
+    fn test_group_by_into_hash_set_groups() {
+        let words = vec!["apple", "avocado", "apple", "banana"];
+        let grouped = words
+            .into_iter()
+            .group_by_into::<BTreeMap<_, HashSet<_>>>(|word| word.len() % 2);
+        assert_eq!(grouped[&1], HashSet::from(["apple", "avocado"]));
+        assert_eq!(grouped[&0], HashSet::from(["banana"]));
+    }
+
+    #[test]
+    fn test_group_by_map_fn() {
+        let users = create_users();
+        let names = group_by_map(users, |user| user.age, |user| user.name);
+        assert_eq!(
+            names,
+            HashMap::from([(20, vec!["Alice", "Bob"]), (30, vec!["Charlie"])])
+        );
+    }
+
+    #[test]
+    fn test_group_by_map_ext() {
+        let numbers = vec![1, 2, 3, 4, 5, 6];
+        let squares = numbers.into_iter().group_by_map(|&n| n % 2, |n| n * n);
+        assert_eq!(
+            squares,
+            HashMap::from([(0, vec![4, 16, 36]), (1, vec![1, 9, 25])])
+        );
+    }
+
+    #[test]
+    fn test_group_by_fold_fn() {
+        let users = create_users();
+        let id_sums = group_by_fold(users, |user| user.age, 0, |sum, user| *sum += user.id);
+        assert_eq!(id_sums, HashMap::from([(20, 3), (30, 3)]));
+    }
+
+    #[test]
+    fn test_group_by_fold_ext_with_non_copy_accumulator() {
+        let words = vec!["apple", "banana", "avocado"];
+        let joined = words.into_iter().group_by_fold(
+            |w| w.chars().next().unwrap(),
+            String::new(),
+            |acc, w| {
+                if !acc.is_empty() {
+                    acc.push(',');
+                }
+                acc.push_str(w);
+            },
+        );
+        assert_eq!(
+            joined,
+            HashMap::from([
+                ('a', "apple,avocado".to_string()),
+                ('b', "banana".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_group_by_nested_fn() {
+        let users = create_users();
+        let tree = group_by_nested(users, (|user: &User| user.age, |user: &User| user.id % 2));
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[&20][&1][0].name, "Alice");
+        assert_eq!(tree[&20][&0][0].name, "Bob");
+        assert_eq!(tree[&30][&1][0].name, "Charlie");
+    }
+
+    #[test]
+    fn test_group_by_nested_ext_three_levels() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let tree = numbers.into_iter().group_by_nested((
+            |n: &i32| n % 2,
+            |n: &i32| n % 3,
+            |n: &i32| *n > 4,
+        ));
+        assert_eq!(tree[&0][&2][&true], vec![8]);
+        assert_eq!(tree[&1][&1][&false], vec![1]);
+        assert_eq!(tree[&1][&1][&true], vec![7]);
+    }
+
+    #[test]
+    fn test_flatten_groups_round_trip() {
+        let users = create_users();
+        let tree = group_by_nested(
+            users.clone(),
+            (|user: &User| user.age, |user: &User| user.name),
+        );
+        let mut rows = flatten_groups(tree);
+        rows.sort_by_key(|(_, _, user)| user.id);
+
+        let expected: Vec<(u32, &str, User)> = users
+            .into_iter()
+            .map(|user| (user.age, user.name, user))
+            .collect();
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn test_flatten_groups_single_level() {
+        let grouped = group_by(vec![1, 2, 3], |&n| n % 2);
+        let mut rows = grouped.flatten_groups();
+        rows.sort();
+        assert_eq!(rows, vec![(0, 2), (1, 1), (1, 3)]);
+    }
+
+    #[test]
+    fn test_try_group_by_fn() {
+        let users = create_users();
+        let grouped = try_group_by(users, |user| user.name.parse::<u32>());
+        assert_eq!(
+            grouped.unwrap_err().kind(),
+            &std::num::IntErrorKind::InvalidDigit
+        );
+    }
+
+    #[test]
+    fn test_try_group_by_ext() {
+        let lines = vec!["a=1", "b=2", "c=1"];
+        let grouped = lines
+            .into_iter()
+            .try_group_by(|line| line[2..].parse::<u32>());
+        assert_eq!(
+            grouped,
+            Ok(HashMap::from([(1, vec!["a=1", "c=1"]), (2, vec!["b=2"])]))
+        );
+    }
+
+    #[test]
+    fn test_try_group_by_stops_at_first_error() {
+        let calls = std::cell::Cell::new(0);
+        let result = try_group_by(vec![1, -1, 2, -2], |&n| {
+            calls.set(calls.get() + 1);
+            if n < 0 { Err(n) } else { Ok(n % 2) }
+        });
+        assert_eq!(result, Err(-1));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_try_group_by_collect_errors() {
+        let result = vec![1, -1, 2, -2]
+            .into_iter()
+            .try_group_by_collect_errors(|&n| if n < 0 { Err(n) } else { Ok(n % 2) });
+        assert_eq!(result, Err(vec![-1, -2]));
+
+        let result = try_group_by_collect_errors(vec![1, 2, 3], |&n| Ok::<_, ()>(n % 2));
+        assert_eq!(result, Ok(HashMap::from([(0, vec![2]), (1, vec![1, 3])])));
+    }
 }
@@ -0,0 +1,643 @@
+use std::any::Any;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Source of the ids that tie every [`IndexKey`] to the [`MultiIndex`] it was
+/// registered on.
+static NEXT_INDEX_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A collection that stores every item once and looks it up in O(1) by several keys.
+///
+/// Where [`key_by`](crate::key_by) builds one HashMap for one key resolver, a
+/// `MultiIndex` keeps one unique index per named key resolver (e.g. `"id"`,
+/// `"email"`, `"slug"`) over the same items, and each index may use its own key
+/// type. [`MultiIndex::insert`] and [`MultiIndex::remove`] keep all indexes
+/// consistent, and an item whose key is already taken in any index is rejected with
+/// a [`UniqueViolation`].
+///
+/// Lookups are bidirectional: any key leads to the item, and through it to every
+/// other key, so [`MultiIndex::translate`] maps e.g. an id to an email and an email
+/// back to an id.
+///
+/// A `MultiIndex` is created with [`MultiIndex::builder`]. Registering a key
+/// resolver returns an [`IndexKey`] handle, which carries the key type and is used
+/// for every lookup. Like `HashMap::get`, lookups accept any borrowed form of the
+/// key, so a `String` index can be queried with a `&str`. Key resolvers must be
+/// `Send + Sync`, so a `MultiIndex` can be shared across threads.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::MultiIndex;
+///
+/// #[derive(Debug)]
+/// struct User { id: u32, email: String }
+///
+/// let users = vec![
+///     User { id: 1, email: "alice@example.com".to_string() },
+///     User { id: 2, email: "bob@example.com".to_string() },
+/// ];
+/// let mut builder = MultiIndex::builder(users);
+/// let by_id = builder.key("id", |user: &User| user.id);
+/// let by_email = builder.key("email", |user: &User| user.email.clone());
+/// let mut index = builder.build().unwrap();
+///
+/// assert_eq!(index.get(&by_email, "bob@example.com").map(|u| u.id), Some(2));
+/// assert_eq!(index.translate(&by_id, &2, &by_email).as_deref(), Some("bob@example.com"));
+///
+/// let removed = index.remove(&by_id, &1).unwrap();
+/// assert_eq!(removed.email, "alice@example.com");
+/// assert!(index.get(&by_email, "alice@example.com").is_none());
+/// ```
+pub struct MultiIndex<T> {
+    id: usize,
+    slots: Vec<Option<T>>,
+    free_slots: Vec<usize>,
+    indexes: Vec<Box<dyn KeyIndex<T>>>,
+}
+
+/// Builds a [`MultiIndex`] from items and named key resolvers.
+///
+/// This struct is created by [`MultiIndex::builder`].
+pub struct MultiIndexBuilder<T> {
+    id: usize,
+    items: Vec<T>,
+    indexes: Vec<Box<dyn KeyIndex<T>>>,
+    duplicate_name: Option<&'static str>,
+}
+
+/// A handle to one index of a [`MultiIndex`], typed by the key of that index.
+///
+/// This struct is returned by [`MultiIndexBuilder::key`].
+pub struct IndexKey<T, K> {
+    owner: usize,
+    position: usize,
+    name: &'static str,
+    resolver: Arc<dyn Fn(&T) -> K + Send + Sync>,
+}
+
+/// An item rejected by a [`MultiIndex`] because one of its keys was already taken.
+///
+/// The clashing key can be recovered with [`IndexKey::key_of`] on the handle of
+/// the index named by `index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueViolation<T> {
+    /// The name of the index in which the key was already taken.
+    pub index: &'static str,
+    /// The rejected item.
+    pub item: T,
+}
+
+/// The reasons [`MultiIndexBuilder::build`] can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexBuildError<T> {
+    /// No key resolver was registered.
+    NoKeys,
+    /// Two key resolvers were registered under the same name.
+    DuplicateName(&'static str),
+    /// Some items had a key that was already taken by an earlier item.
+    Violations(Vec<UniqueViolation<T>>),
+}
+
+impl<T> fmt::Display for UniqueViolation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a key is already taken in index {:?}", self.index)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for UniqueViolation<T> {}
+
+impl<T> fmt::Display for IndexBuildError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexBuildError::NoKeys => write!(f, "at least one key resolver is required"),
+            IndexBuildError::DuplicateName(name) => {
+                write!(f, "index {name:?} is registered more than once")
+            }
+            IndexBuildError::Violations(violations) => {
+                write!(
+                    f,
+                    "{} items have a key that is already taken",
+                    violations.len()
+                )
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for IndexBuildError<T> {}
+
+/// One unique index of a [`MultiIndex`], with its key type erased so that indexes
+/// with different key types can be stored together.
+///
+/// Keys cross this boundary as `dyn Any` and are always of the index's own key
+/// type, since they are only ever produced by [`KeyIndex::resolve`].
+trait KeyIndex<T>: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Runs the key resolver on `item`.
+    fn resolve(&self, item: &T) -> Box<dyn Any>;
+
+    /// Returns `true` if a key returned by [`KeyIndex::resolve`] is already taken.
+    fn contains(&self, key: &dyn Any) -> bool;
+
+    /// Stores a key returned by [`KeyIndex::resolve`] under `slot`.
+    fn insert(&mut self, key: Box<dyn Any>, slot: usize);
+
+    fn remove(&mut self, item: &T);
+
+    /// Returns the underlying `HashMap<K, usize>` from keys to slots.
+    fn slots(&self) -> &dyn Any;
+}
+
+/// A [`KeyIndex`] for keys of type `K` produced by the resolver `R`.
+struct TypedIndex<K, R> {
+    name: &'static str,
+    resolver: Arc<R>,
+    slots: HashMap<K, usize>,
+}
+
+impl<T, K, R> KeyIndex<T> for TypedIndex<K, R>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    R: Fn(&T) -> K + Send + Sync,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn resolve(&self, item: &T) -> Box<dyn Any> {
+        Box::new((self.resolver)(item))
+    }
+
+    fn contains(&self, key: &dyn Any) -> bool {
+        let key: &K = key
+            .downcast_ref()
+            .expect("keys are resolved by their own index");
+        self.slots.contains_key(key)
+    }
+
+    fn insert(&mut self, key: Box<dyn Any>, slot: usize) {
+        let key = key
+            .downcast()
+            .expect("keys are resolved by their own index");
+        self.slots.insert(*key, slot);
+    }
+
+    fn remove(&mut self, item: &T) {
+        self.slots.remove(&(self.resolver)(item));
+    }
+
+    fn slots(&self) -> &dyn Any {
+        &self.slots
+    }
+}
+
+impl<T> MultiIndex<T> {
+    /// Starts building an index over `items`.
+    ///
+    /// Register one or more key resolvers with [`MultiIndexBuilder::key`] and
+    /// finish with [`MultiIndexBuilder::build`].
+    pub fn builder(items: impl IntoIterator<Item = T>) -> MultiIndexBuilder<T> {
+        MultiIndexBuilder {
+            id: NEXT_INDEX_ID.fetch_add(1, Ordering::Relaxed),
+            items: items.into_iter().collect(),
+            indexes: Vec::new(),
+            duplicate_name: None,
+        }
+    }
+
+    /// Returns the item whose key in the index of `handle` equals `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` was registered on a different `MultiIndex`.
+    pub fn get<K, Q>(&self, handle: &IndexKey<T, K>, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q> + Hash + Eq + 'static,
+        Q: ?Sized + Hash + Eq,
+    {
+        let slot = self.slot(handle, key)?;
+        self.slots[slot].as_ref()
+    }
+
+    /// Returns `true` if the index of `handle` contains `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` was registered on a different `MultiIndex`.
+    pub fn contains_key<K, Q>(&self, handle: &IndexKey<T, K>, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Hash + Eq + 'static,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.get(handle, key).is_some()
+    }
+
+    /// Looks up the item whose key in the index of `from` equals `key`, and returns
+    /// its key in the index of `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either handle was registered on a different `MultiIndex`.
+    pub fn translate<K, Q, L>(
+        &self,
+        from: &IndexKey<T, K>,
+        key: &Q,
+        to: &IndexKey<T, L>,
+    ) -> Option<L>
+    where
+        K: Borrow<Q> + Hash + Eq + 'static,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.check_owner(to.owner);
+        self.get(from, key).map(|item| to.key_of(item))
+    }
+
+    /// Inserts an item into every index.
+    ///
+    /// If any of the item's keys is already taken, nothing is changed and the item
+    /// is handed back inside the [`UniqueViolation`].
+    pub fn insert(&mut self, item: T) -> Result<(), UniqueViolation<T>> {
+        // Resolve every key once, and check them all before touching any index so
+        // a rejected item leaves the index unchanged.
+        let keys: Vec<Box<dyn Any>> = self
+            .indexes
+            .iter()
+            .map(|index| index.resolve(&item))
+            .collect();
+        if let Some((index, _)) = self
+            .indexes
+            .iter()
+            .zip(&keys)
+            .find(|(index, key)| index.contains(key.as_ref()))
+        {
+            return Err(UniqueViolation {
+                index: index.name(),
+                item,
+            });
+        }
+
+        let slot = self.free_slots.pop().unwrap_or(self.slots.len());
+        for (index, key) in self.indexes.iter_mut().zip(keys) {
+            index.insert(key, slot);
+        }
+        if slot == self.slots.len() {
+            self.slots.push(Some(item));
+        } else {
+            self.slots[slot] = Some(item);
+        }
+        Ok(())
+    }
+
+    /// Removes the item whose key in the index of `handle` equals `key`, dropping
+    /// its keys from every index.
+    ///
+    /// Returns the removed item, or `None` if there was no match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` was registered on a different `MultiIndex`.
+    pub fn remove<K, Q>(&mut self, handle: &IndexKey<T, K>, key: &Q) -> Option<T>
+    where
+        K: Borrow<Q> + Hash + Eq + 'static,
+        Q: ?Sized + Hash + Eq,
+    {
+        let slot = self.slot(handle, key)?;
+        let item = self.slots[slot].take()?;
+        for index in &mut self.indexes {
+            index.remove(&item);
+        }
+        self.free_slots.push(slot);
+        Some(item)
+    }
+
+    /// Returns the number of stored items.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    /// Returns `true` if no items are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the stored items in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().flatten()
+    }
+
+    /// Returns the names of the indexes, in registration order.
+    pub fn index_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.indexes.iter().map(|index| index.name())
+    }
+
+    fn slot<K, Q>(&self, handle: &IndexKey<T, K>, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q> + Hash + Eq + 'static,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.check_owner(handle.owner);
+        let slots: &HashMap<K, usize> = self.indexes[handle.position]
+            .slots()
+            .downcast_ref()
+            .expect("a handle's key type matches its index");
+        slots.get(key).copied()
+    }
+
+    fn check_owner(&self, owner: usize) {
+        assert_eq!(
+            owner, self.id,
+            "the index key handle belongs to a different MultiIndex"
+        );
+    }
+}
+
+impl<T> MultiIndexBuilder<T> {
+    /// Registers a unique index named `name` whose keys come from `key_resolver`,
+    /// returning the handle used to look items up by that key.
+    ///
+    /// Names must be distinct; a repeated name makes [`MultiIndexBuilder::build`]
+    /// fail with [`IndexBuildError::DuplicateName`].
+    pub fn key<K>(
+        &mut self,
+        name: &'static str,
+        key_resolver: impl Fn(&T) -> K + Send + Sync + 'static,
+    ) -> IndexKey<T, K>
+    where
+        K: Hash + Eq + Send + Sync + 'static,
+    {
+        if self.duplicate_name.is_none() && self.indexes.iter().any(|index| index.name() == name) {
+            self.duplicate_name = Some(name);
+        }
+
+        let resolver = Arc::new(key_resolver);
+        self.indexes.push(Box::new(TypedIndex {
+            name,
+            resolver: Arc::clone(&resolver),
+            slots: HashMap::new(),
+        }));
+        IndexKey {
+            owner: self.id,
+            position: self.indexes.len() - 1,
+            name,
+            resolver,
+        }
+    }
+
+    /// Builds the index, inserting the items in input order.
+    ///
+    /// Fails with [`IndexBuildError::NoKeys`] if no key resolver was registered,
+    /// [`IndexBuildError::DuplicateName`] if a name was registered twice, or
+    /// [`IndexBuildError::Violations`] with every item whose key was already taken
+    /// by an earlier item.
+    pub fn build(self) -> Result<MultiIndex<T>, IndexBuildError<T>> {
+        if self.indexes.is_empty() {
+            return Err(IndexBuildError::NoKeys);
+        }
+        if let Some(name) = self.duplicate_name {
+            return Err(IndexBuildError::DuplicateName(name));
+        }
+
+        let mut index = MultiIndex {
+            id: self.id,
+            slots: Vec::with_capacity(self.items.len()),
+            free_slots: Vec::new(),
+            indexes: self.indexes,
+        };
+
+        let violations: Vec<UniqueViolation<T>> = self
+            .items
+            .into_iter()
+            .filter_map(|item| index.insert(item).err())
+            .collect();
+
+        if violations.is_empty() {
+            Ok(index)
+        } else {
+            Err(IndexBuildError::Violations(violations))
+        }
+    }
+}
+
+impl<T, K> IndexKey<T, K> {
+    /// Returns the name this index was registered under.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the key of `item` in this index.
+    pub fn key_of(&self, item: &T) -> K {
+        (self.resolver)(item)
+    }
+}
+
+impl<T, K> Clone for IndexKey<T, K> {
+    fn clone(&self) -> Self {
+        IndexKey {
+            owner: self.owner,
+            position: self.position,
+            name: self.name,
+            resolver: Arc::clone(&self.resolver),
+        }
+    }
+}
+
+impl<T, K> fmt::Debug for IndexKey<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexKey")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MultiIndex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.index_names().collect();
+        f.debug_struct("MultiIndex")
+            .field("indexes", &names)
+            .field("items", &self.slots.iter().flatten().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        id: u32,
+        email: &'static str,
+        slug: &'static str,
+    }
+
+    struct Keys {
+        id: IndexKey<User, u32>,
+        email: IndexKey<User, String>,
+        slug: IndexKey<User, &'static str>,
+    }
+
+    fn create_users() -> Vec<User> {
+        vec![
+            User {
+                id: 1,
+                email: "alice@example.com",
+                slug: "alice",
+            },
+            User {
+                id: 2,
+                email: "bob@example.com",
+                slug: "bob",
+            },
+        ]
+    }
+
+    fn build_index(users: Vec<User>) -> (Result<MultiIndex<User>, IndexBuildError<User>>, Keys) {
+        let mut builder = MultiIndex::builder(users);
+        let keys = Keys {
+            id: builder.key("id", |user: &User| user.id),
+            email: builder.key("email", |user: &User| user.email.to_string()),
+            slug: builder.key("slug", |user: &User| user.slug),
+        };
+        (builder.build(), keys)
+    }
+
+    #[test]
+    fn test_index_lookup_by_every_key() {
+        let (index, keys) = build_index(create_users());
+        let index = index.unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get(&keys.id, &1).map(|u| u.slug), Some("alice"));
+        assert_eq!(
+            index.get(&keys.email, "bob@example.com").map(|u| u.id),
+            Some(2)
+        );
+        assert_eq!(index.get(&keys.slug, &"bob").map(|u| u.id), Some(2));
+        assert_eq!(index.get(&keys.id, &3), None);
+        assert_eq!(
+            index.index_names().collect::<Vec<_>>(),
+            vec!["id", "email", "slug"]
+        );
+    }
+
+    #[test]
+    fn test_index_translates_between_keys() {
+        let (index, keys) = build_index(create_users());
+        let index = index.unwrap();
+        assert_eq!(
+            index.translate(&keys.id, &1, &keys.email),
+            Some("alice@example.com".to_string())
+        );
+        assert_eq!(
+            index.translate(&keys.email, "bob@example.com", &keys.id),
+            Some(2)
+        );
+        assert_eq!(index.translate(&keys.slug, &"carol", &keys.id), None);
+    }
+
+    #[test]
+    fn test_index_build_reports_violations() {
+        let mut users = create_users();
+        users.push(User {
+            id: 3,
+            email: "alice@example.com",
+            slug: "carol",
+        });
+        let (result, keys) = build_index(users);
+        let Err(IndexBuildError::Violations(violations)) = result else {
+            panic!("expected violations");
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].index, "email");
+        assert_eq!(violations[0].item.id, 3);
+        assert_eq!(keys.email.key_of(&violations[0].item), "alice@example.com");
+    }
+
+    #[test]
+    fn test_index_build_rejects_bad_registrations() {
+        let builder = MultiIndex::<User>::builder(create_users());
+        assert_eq!(builder.build().unwrap_err(), IndexBuildError::NoKeys);
+
+        let mut builder = MultiIndex::builder(create_users());
+        builder.key("id", |user: &User| user.id);
+        builder.key("id", |user: &User| user.slug);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            IndexBuildError::DuplicateName("id")
+        );
+    }
+
+    #[test]
+    fn test_index_insert_rejects_without_partial_update() {
+        let (index, keys) = build_index(create_users());
+        let mut index = index.unwrap();
+        let duplicate = User {
+            id: 3,
+            email: "carol@example.com",
+            slug: "bob",
+        };
+        let violation = index.insert(duplicate.clone()).unwrap_err();
+        assert_eq!(violation.index, "slug");
+        assert_eq!(violation.item, duplicate);
+        assert!(!index.contains_key(&keys.id, &3));
+        assert!(!index.contains_key(&keys.email, "carol@example.com"));
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_index_remove_keeps_indexes_consistent() {
+        let (index, keys) = build_index(create_users());
+        let mut index = index.unwrap();
+        let removed = index.remove(&keys.slug, &"alice").unwrap();
+        assert_eq!(removed.id, 1);
+        assert!(!index.contains_key(&keys.id, &1));
+        assert!(!index.contains_key(&keys.email, "alice@example.com"));
+        assert_eq!(index.remove(&keys.slug, &"alice"), None);
+
+        // The freed keys and slot can be reused.
+        index.insert(removed).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.iter().count(), 2);
+        assert!(index.contains_key(&keys.email, "alice@example.com"));
+    }
+
+    #[test]
+    #[should_panic(expected = "belongs to a different MultiIndex")]
+    fn test_index_rejects_foreign_handle() {
+        let (_, keys) = build_index(create_users());
+        let (index, _) = build_index(create_users());
+        index.unwrap().get(&keys.id, &1);
+    }
+
+    #[test]
+    fn test_index_resolves_each_key_once_per_insert() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let mut builder = MultiIndex::builder(create_users());
+        let by_id = builder.key("id", |user: &User| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            user.id
+        });
+        let mut index = builder.build().unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+        let removed = index.remove(&by_id, &1).unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 3);
+        index.insert(removed).unwrap();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_index_can_be_shared_across_threads() {
+        let (index, keys) = build_index(create_users());
+        let index = std::sync::Arc::new(index.unwrap());
+        let shared = std::sync::Arc::clone(&index);
+        let handle =
+            std::thread::spawn(move || shared.get(&keys.email, "bob@example.com").map(|u| u.id));
+        assert_eq!(handle.join().unwrap(), Some(2));
+    }
+}
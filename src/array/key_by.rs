@@ -1,6 +1,10 @@
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
 use std::hash::Hash;
 
+use crate::collection::MapCollection;
+
 /// Creates a HashMap from an iterable collection by using a key resolver function.
 ///
 /// This function takes any iterable collection and transforms it into a HashMap
@@ -45,6 +49,306 @@ where
         .collect()
 }
 
+/// Creates any map type from an iterable collection by using a key resolver function.
+///
+/// This works like [`key_by`], but the resulting map type `M` is chosen by the
+/// caller, e.g. a `BTreeMap` for sorted keys or a `HashMap` with a custom
+/// `BuildHasher`. Later items with the same key overwrite earlier ones.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key
+///
+/// # Returns
+///
+/// A map of type `M` from each key to the last item that produced it
+///
+/// # Type Parameters
+///
+/// * `M` - The map type to collect into (must implement [`MapCollection`])
+/// * `T` - The type of items in the input collection
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use rust_toolkit::key_by_into;
+///
+/// let people = vec!["Charlie", "Alice", "Bob"];
+/// let by_length: BTreeMap<_, _> = key_by_into(people, |name| name.len());
+/// assert_eq!(by_length, BTreeMap::from([(3, "Bob"), (5, "Alice"), (7, "Charlie")]));
+/// ```
+pub fn key_by_into<M, T>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> M::Key,
+) -> M
+where
+    M: MapCollection<Value = T>,
+{
+    let mut map = M::default();
+    for item in items {
+        map.insert(key_resolver(&item), item);
+    }
+    map
+}
+
+/// Creates a HashMap from an iterable collection using a fallible key resolver
+/// function, stopping at the first error.
+///
+/// This works like [`key_by`], but `key_resolver` returns a `Result`, which is
+/// useful when keys are parsed from the items. Later items with the same key
+/// overwrite earlier ones.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, T>`, or the first error returned by `key_resolver`
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::try_key_by;
+///
+/// let records = vec!["1,Alice", "2,Bob"];
+/// let by_id = try_key_by(records, |r| r.split(',').next().unwrap().parse::<u32>());
+/// assert_eq!(by_id, Ok(HashMap::from([(1, "1,Alice"), (2, "2,Bob")])));
+/// ```
+pub fn try_key_by<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, T>, E>
+where
+    K: Hash + Eq,
+{
+    let mut map = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item)?;
+        map.insert(key, item);
+    }
+    Ok(map)
+}
+
+/// Like [`try_key_by`], but keeps going after an error and returns every error
+/// produced by `key_resolver`, in input order.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns `Ok(key)` or an error
+///
+/// # Returns
+///
+/// `Ok` with the resulting `HashMap<K, T>` if every key resolved, otherwise `Err` with all errors
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::try_key_by_collect_errors;
+///
+/// let records = vec!["1,Alice", "?,Bob", "!,Carol"];
+/// let errors = try_key_by_collect_errors(records, |r| r.split(',').next().unwrap().parse::<u32>())
+///     .unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn try_key_by_collect_errors<T, K, E>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> Result<K, E>,
+) -> Result<HashMap<K, T>, Vec<E>>
+where
+    K: Hash + Eq,
+{
+    let mut map = HashMap::new();
+    let mut errors = Vec::new();
+    for item in items {
+        match key_resolver(&item) {
+            // Once an error has been seen the map is discarded, so stop filling it
+            Ok(key) if errors.is_empty() => {
+                map.insert(key, item);
+            }
+            Ok(_) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+    if errors.is_empty() {
+        Ok(map)
+    } else {
+        Err(errors)
+    }
+}
+
+/// What [`key_by_with`] does when two items produce the same key.
+pub enum CollisionPolicy<'a, T> {
+    /// Keep the item that was seen first.
+    KeepFirst,
+    /// Keep the item that was seen last, like [`key_by`].
+    KeepLast,
+    /// Fail with every key that was produced more than once.
+    Error,
+    /// Combine the existing item with the new one, in that order.
+    Merge(Box<dyn Fn(T, T) -> T + 'a>),
+}
+
+impl<'a, T> CollisionPolicy<'a, T> {
+    /// Creates a [`CollisionPolicy::Merge`] policy from a merge function.
+    ///
+    /// The function receives the item already stored for the key followed by the
+    /// newly seen item, and returns the item to store.
+    pub fn merge(merge: impl Fn(T, T) -> T + 'a) -> Self {
+        CollisionPolicy::Merge(Box::new(merge))
+    }
+}
+
+impl<T> fmt::Debug for CollisionPolicy<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollisionPolicy::KeepFirst => write!(f, "KeepFirst"),
+            CollisionPolicy::KeepLast => write!(f, "KeepLast"),
+            CollisionPolicy::Error => write!(f, "Error"),
+            CollisionPolicy::Merge(_) => write!(f, "Merge(..)"),
+        }
+    }
+}
+
+/// The error returned by [`key_by_with`] under [`CollisionPolicy::Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKeys<K> {
+    /// Every key produced by more than one item, in the order the first
+    /// collision for each key was found.
+    pub keys: Vec<K>,
+}
+
+impl<K: fmt::Debug> fmt::Display for DuplicateKeys<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate keys: {:?}", self.keys)
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for DuplicateKeys<K> {}
+
+/// Creates a HashMap from an iterable collection by using a key resolver function,
+/// handling items that share a key according to `policy`.
+///
+/// [`key_by`] silently keeps the last item for a repeated key; this function makes
+/// that choice explicit and can instead keep the first item, merge the items, or
+/// report every duplicated key as an error.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+/// * `policy` - How to handle two items with the same key
+///
+/// # Returns
+///
+/// `Ok` with the HashMap, or [`DuplicateKeys`] listing every repeated key when
+/// `policy` is [`CollisionPolicy::Error`]
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys in the resulting HashMap (must implement Hash + Eq)
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::{CollisionPolicy, DuplicateKeys, key_by_with};
+///
+/// let items = vec![("a", 1), ("b", 2), ("a", 3)];
+///
+/// let first = key_by_with(items.clone(), |&(k, _)| k, CollisionPolicy::KeepFirst);
+/// assert_eq!(first, Ok(HashMap::from([("a", ("a", 1)), ("b", ("b", 2))])));
+///
+/// let sum = CollisionPolicy::merge(|(k, a): (&str, i32), (_, b)| (k, a + b));
+/// let merged = key_by_with(items.clone(), |&(k, _)| k, sum);
+/// assert_eq!(merged, Ok(HashMap::from([("a", ("a", 4)), ("b", ("b", 2))])));
+///
+/// let error = key_by_with(items, |&(k, _)| k, CollisionPolicy::Error);
+/// assert_eq!(error, Err(DuplicateKeys { keys: vec!["a"] }));
+/// ```
+pub fn key_by_with<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+    policy: CollisionPolicy<'_, T>,
+) -> Result<HashMap<K, T>, DuplicateKeys<K>>
+where
+    K: Hash + Eq,
+{
+    if let CollisionPolicy::Error = policy {
+        return key_by_strict(items, key_resolver);
+    }
+
+    let mut map: HashMap<K, T> = HashMap::new();
+    for item in items {
+        let key = key_resolver(&item);
+        let mut entry = match map.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(item);
+                continue;
+            }
+            Entry::Occupied(entry) => entry,
+        };
+
+        match &policy {
+            CollisionPolicy::KeepFirst => {}
+            CollisionPolicy::KeepLast => {
+                entry.insert(item);
+            }
+            CollisionPolicy::Merge(merge) => {
+                let (key, existing) = entry.remove_entry();
+                map.insert(key, merge(existing, item));
+            }
+            CollisionPolicy::Error => unreachable!("handled by key_by_strict"),
+        }
+    }
+    Ok(map)
+}
+
+/// Implements [`CollisionPolicy::Error`] for [`key_by_with`].
+///
+/// Once a key collides the result is an error, so the map only serves to detect
+/// repeats: the colliding key is moved out of it into the report instead of being
+/// cloned, along with the position of its first collision so the report keeps
+/// that order.
+fn key_by_strict<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Result<HashMap<K, T>, DuplicateKeys<K>>
+where
+    K: Hash + Eq,
+{
+    let mut map: HashMap<K, T> = HashMap::new();
+    let mut reported: HashMap<K, usize> = HashMap::new();
+
+    for item in items {
+        match map.entry(key_resolver(&item)) {
+            Entry::Vacant(entry) => {
+                entry.insert(item);
+            }
+            Entry::Occupied(entry) => {
+                let (key, _) = entry.remove_entry();
+                let position = reported.len();
+                reported.entry(key).or_insert(position);
+            }
+        }
+    }
+
+    if reported.is_empty() {
+        return Ok(map);
+    }
+    let mut duplicates: Vec<(K, usize)> = reported.into_iter().collect();
+    duplicates.sort_unstable_by_key(|&(_, position)| position);
+    Err(DuplicateKeys {
+        keys: duplicates.into_iter().map(|(key, _)| key).collect(),
+    })
+}
+
 /// Extension trait that adds the `key_by` method to any iterator.
 ///
 /// This trait provides a convenient way to transform iterators into HashMaps
@@ -85,6 +389,112 @@ pub trait KeyByExt: Iterator {
     {
         key_by(self, key_resolver)
     }
+
+    /// Transforms the iterator into any map type using a key resolver function.
+    ///
+    /// See [`key_by_into`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_resolver` - A function that takes a reference to an item and returns a key
+    ///
+    /// # Returns
+    ///
+    /// A map of type `M` from each key to the last item that produced it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use rust_toolkit::KeyByExt;
+    ///
+    /// let numbers = vec![3, 1, 2];
+    /// let by_value = numbers.into_iter().key_by_into::<BTreeMap<_, _>>(|&n| n * 10);
+    /// assert_eq!(by_value.into_iter().collect::<Vec<_>>(), vec![(10, 1), (20, 2), (30, 3)]);
+    /// ```
+    fn key_by_into<M>(self, key_resolver: impl Fn(&Self::Item) -> M::Key) -> M
+    where
+        Self: Sized,
+        M: MapCollection<Value = Self::Item>,
+    {
+        key_by_into(self, key_resolver)
+    }
+
+    /// Like `key_by`, but with a fallible key resolver; stops at the first error.
+    ///
+    /// See [`try_key_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::KeyByExt;
+    ///
+    /// let by_id = vec!["7", "x"].into_iter().try_key_by(|s| s.parse::<u32>());
+    /// assert!(by_id.is_err());
+    /// ```
+    fn try_key_by<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, Self::Item>, E>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_key_by(self, key_resolver)
+    }
+
+    /// Like `key_by`, but with a fallible key resolver; returns every error.
+    ///
+    /// See [`try_key_by_collect_errors`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::KeyByExt;
+    ///
+    /// let by_id = vec!["7", "8"].into_iter().try_key_by_collect_errors(|s| s.parse::<u32>());
+    /// assert_eq!(by_id.unwrap()[&8], "8");
+    /// ```
+    fn try_key_by_collect_errors<K, E>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> Result<K, E>,
+    ) -> Result<HashMap<K, Self::Item>, Vec<E>>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        try_key_by_collect_errors(self, key_resolver)
+    }
+
+    /// Transforms the iterator into a HashMap using a key resolver function,
+    /// handling items that share a key according to `policy`.
+    ///
+    /// See [`key_by_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::{CollisionPolicy, KeyByExt};
+    ///
+    /// let numbers = vec![1, 2, 3, 4, 5];
+    /// let by_parity = numbers
+    ///     .into_iter()
+    ///     .key_by_with(|&n| n % 2, CollisionPolicy::KeepFirst)
+    ///     .unwrap();
+    /// assert_eq!(by_parity[&1], 1);
+    /// assert_eq!(by_parity[&0], 2);
+    /// ```
+    fn key_by_with<K>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+        policy: CollisionPolicy<'_, Self::Item>,
+    ) -> Result<HashMap<K, Self::Item>, DuplicateKeys<K>>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+    {
+        key_by_with(self, key_resolver, policy)
+    }
 }
 
 /// Blanket implementation of KeyByExt for all iterator types.
@@ -96,6 +506,8 @@ impl<I: Iterator> KeyByExt for I {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
+    use std::hash::{BuildHasherDefault, DefaultHasher};
 
     #[derive(Debug, Clone, PartialEq)]
     struct User {
@@ -168,4 +580,104 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_key_by_into_btree_map() {
+        let users = create_users();
+        let user_map: BTreeMap<_, _> = key_by_into(users, |user| user.name);
+        assert_eq!(
+            user_map.keys().copied().collect::<Vec<_>>(),
+            vec!["Alice", "Bob", "Charlie"]
+        );
+    }
+
+    #[test]
+    fn test_key_by_into_custom_hasher() {
+        let users = create_users();
+        let user_map = users
+            .into_iter()
+            .key_by_into::<HashMap<_, _, BuildHasherDefault<DefaultHasher>>>(|user| user.id);
+        assert_eq!(user_map[&2], User { id: 2, name: "Bob" });
+    }
+
+    #[test]
+    fn test_try_key_by_fn() {
+        let users = create_users();
+        let user_map = try_key_by(users, |user| user.name.parse::<char>());
+        assert!(user_map.is_err());
+    }
+
+    #[test]
+    fn test_try_key_by_ext() {
+        let users = create_users();
+        let user_map = users
+            .into_iter()
+            .try_key_by(|user| Ok::<_, String>(user.id));
+        assert_eq!(user_map.unwrap()[&2], User { id: 2, name: "Bob" });
+    }
+
+    #[test]
+    fn test_try_key_by_collect_errors() {
+        let users = create_users();
+        let result = try_key_by_collect_errors(users, |user| {
+            if user.id == 2 {
+                Ok(user.id)
+            } else {
+                Err(user.name)
+            }
+        });
+        assert_eq!(result, Err(vec!["Alice", "Charlie"]));
+    }
+
+    #[test]
+    fn test_key_by_with_keep_first_and_last() {
+        let items = vec![(1, "a"), (2, "b"), (1, "c")];
+
+        let first = key_by_with(items.clone(), |&(k, _)| k, CollisionPolicy::KeepFirst);
+        assert_eq!(first, Ok(HashMap::from([(1, (1, "a")), (2, (2, "b"))])));
+
+        let last = key_by_with(items.clone(), |&(k, _)| k, CollisionPolicy::KeepLast);
+        assert_eq!(last, Ok(key_by(items, |&(k, _)| k)));
+    }
+
+    #[test]
+    fn test_key_by_with_accepts_non_clone_keys() {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Key(u32);
+
+        let items = vec![(1, "a"), (2, "b"), (1, "c")];
+        let first = key_by_with(items.clone(), |&(k, _)| Key(k), CollisionPolicy::KeepFirst);
+        assert_eq!(first.unwrap()[&Key(1)], (1, "a"));
+
+        let error = key_by_with(items, |&(k, _)| Key(k), CollisionPolicy::Error);
+        assert_eq!(error, Err(DuplicateKeys { keys: vec![Key(1)] }));
+    }
+
+    #[test]
+    fn test_key_by_with_error_reports_every_duplicate_once() {
+        let items = vec!["a", "b", "a", "c", "b", "a"];
+        let result = items
+            .into_iter()
+            .key_by_with(|&s| s, CollisionPolicy::Error);
+        assert_eq!(
+            result,
+            Err(DuplicateKeys {
+                keys: vec!["a", "b"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_key_by_with_merge() {
+        let users = create_users();
+        let names = key_by_with(
+            users.into_iter().map(|user| user.name.to_string()),
+            |name| name.len() % 2,
+            CollisionPolicy::merge(|a, b| format!("{a}+{b}")),
+        );
+        assert_eq!(
+            names,
+            Ok(HashMap::from([(1, "Alice+Bob+Charlie".to_string())]))
+        );
+    }
 }
@@ -1,13 +1,62 @@
 pub mod chunk;
+pub mod chunk_by;
 pub mod count_by;
+pub mod counter;
 pub mod group_by;
+pub mod index;
 pub mod key_by;
+pub mod partition;
 pub mod remove;
+pub mod set_ops;
+pub mod sketch;
+pub mod top_k;
 pub mod uniq;
+pub mod windows;
+pub mod zip;
 
-pub use chunk::{ChunkExt, chunk};
-pub use count_by::{CountByExt, count_by};
-pub use group_by::{GroupByExt, group_by};
-pub use key_by::{KeyByExt, key_by};
-pub use remove::{RemoveExt, remove};
-pub use uniq::{UniqExt, uniq};
+pub use chunk::{
+    ChunkExt, Chunks, OversizedPolicy, chunk, chunk_array, chunk_by_weight, chunks_lazy, try_chunk,
+    try_chunks_lazy,
+};
+pub use chunk_by::{
+    ChunkBy, ChunkByExt, SplitWhen, chunk_by, chunk_by_lazy, split_when, split_when_lazy,
+};
+pub use count_by::{
+    CountByExt, SumByKeyExt, count_by, count_by_into, sum_by_key, try_count_by,
+    try_count_by_collect_errors,
+};
+pub use counter::{Counter, counter_by};
+pub use group_by::{
+    FlattenGroups, GroupByExt, NestedKeyResolvers, flatten_groups, group_by, group_by_fold,
+    group_by_into, group_by_map, group_by_nested, group_by_ordered, try_group_by,
+    try_group_by_collect_errors,
+};
+pub use index::{IndexBuildError, IndexKey, MultiIndex, MultiIndexBuilder, UniqueViolation};
+pub use key_by::{
+    CollisionPolicy, DuplicateKeys, KeyByExt, key_by, key_by_into, key_by_with, try_key_by,
+    try_key_by_collect_errors,
+};
+pub use partition::{
+    PartitionExt, PartitionKey, Partitions, partition_array, partition_by_key, partition_n,
+    try_partition_array, try_partition_by_key, try_partition_n,
+};
+pub use remove::{RemoveExt, VecRemoveExt, pull_at, remove, remove_range, remove_with_index};
+pub use set_ops::{
+    SetOpsExt, difference, difference_by, difference_sorted, difference_with, intersection,
+    intersection_by, intersection_sorted, intersection_with, union, union_by, union_sorted,
+    union_with, xor, xor_by, xor_sorted, xor_with,
+};
+pub use sketch::{
+    CountMinSketch, DEFAULT_PRECISION, HyperLogLog, MAX_PRECISION, MIN_PRECISION,
+    approx_uniq_count, count_min_sketch, hyper_log_log,
+};
+pub use top_k::{HeavyHitter, count_by_top_k};
+pub use uniq::{
+    Dedup, Duplicates, UniqExt, Unique, UniqueRefs, dedup, duplicates, uniq, uniq_by, uniq_last,
+    uniq_with, unique, unique_refs,
+};
+pub use windows::{Windows, WindowsExt, try_windows, windows};
+pub use zip::{
+    Unzip, Zip, ZipExt, ZipLongest, ZipSources, unzip, zip, zip_longest, zip_object,
+    zip_object_into, zip_with,
+};
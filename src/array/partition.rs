@@ -0,0 +1,563 @@
+use std::marker::PhantomData;
+use std::ops;
+
+use crate::error::{Error, Result};
+
+/// Splits an iterable into `n` buckets in a single pass, routing each item to the
+/// bucket whose position is returned by `bucket_resolver`.
+///
+/// This generalizes [`remove`](crate::remove) from two buckets to many, without
+/// the hashing overhead of [`group_by`](crate::group_by). Items keep their input
+/// order within each bucket, and every bucket is present in the output even if
+/// it stays empty.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `n` - The number of buckets (must be greater than 0)
+/// * `bucket_resolver` - Returns the bucket of an item, in `0..n`
+///
+/// # Returns
+///
+/// A `Vec<Vec<T>>` with exactly `n` buckets
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::partition_n;
+///
+/// let buckets = partition_n(1..=7, 3, |&n| n % 3);
+/// assert_eq!(buckets, vec![vec![3, 6], vec![1, 4, 7], vec![2, 5]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `n` is 0 or if `bucket_resolver` returns a bucket outside `0..n`.
+pub fn partition_n<T>(
+    items: impl IntoIterator<Item = T>,
+    n: usize,
+    bucket_resolver: impl Fn(&T) -> usize,
+) -> Vec<Vec<T>> {
+    try_partition_n(items, n, bucket_resolver).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Splits an iterable into `n` buckets, returning an error instead of panicking
+/// when `n` is 0 or an item is routed to a missing bucket.
+///
+/// This is the fallible counterpart of [`partition_n`].
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `n` - The number of buckets
+/// * `bucket_resolver` - Returns the bucket of an item
+///
+/// # Returns
+///
+/// `Ok` with exactly `n` buckets, [`Error::ZeroSize`] if `n` is 0, or
+/// [`Error::BucketOutOfRange`] for the first item routed outside `0..n`
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{Error, try_partition_n};
+///
+/// assert_eq!(try_partition_n([1, 2], 2, |&n| n % 2), Ok(vec![vec![2], vec![1]]));
+/// assert_eq!(
+///     try_partition_n([1, 2], 2, |&n| n),
+///     Err(Error::BucketOutOfRange { index: 1, bucket: 2, buckets: 2 })
+/// );
+/// ```
+pub fn try_partition_n<T>(
+    items: impl IntoIterator<Item = T>,
+    n: usize,
+    bucket_resolver: impl Fn(&T) -> usize,
+) -> Result<Vec<Vec<T>>> {
+    if n == 0 {
+        return Err(Error::ZeroSize);
+    }
+
+    let mut buckets: Vec<Vec<T>> = (0..n).map(|_| Vec::new()).collect();
+    fill_buckets(&mut buckets, items, bucket_resolver)?;
+    Ok(buckets)
+}
+
+/// Splits an iterable into a fixed number of buckets known at compile time.
+///
+/// See [`partition_n`] for details. Returning an array lets the buckets be
+/// destructured directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::partition_array;
+///
+/// let [small, medium, large] = partition_array(vec![1, 50, 500, 5], |&n: &i32| match n {
+///     ..10 => 0,
+///     ..100 => 1,
+///     _ => 2,
+/// });
+/// assert_eq!(small, vec![1, 5]);
+/// assert_eq!(medium, vec![50]);
+/// assert_eq!(large, vec![500]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `bucket_resolver` returns a bucket outside `0..N`. `N == 0` is
+/// rejected at compile time.
+pub fn partition_array<T, const N: usize>(
+    items: impl IntoIterator<Item = T>,
+    bucket_resolver: impl Fn(&T) -> usize,
+) -> [Vec<T>; N] {
+    try_partition_array(items, bucket_resolver).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Splits an iterable into a fixed number of buckets known at compile time,
+/// returning an error instead of panicking when an item is routed to a missing
+/// bucket.
+///
+/// This is the fallible counterpart of [`partition_array`].
+///
+/// # Returns
+///
+/// `Ok` with the `N` buckets, or [`Error::BucketOutOfRange`] for the first item
+/// routed outside `0..N`
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{Error, try_partition_array};
+///
+/// let result = try_partition_array::<_, 2>(["a", "bb", "ccc"], |s| s.len() - 1);
+/// assert_eq!(
+///     result,
+///     Err(Error::BucketOutOfRange { index: 2, bucket: 2, buckets: 2 })
+/// );
+/// ```
+pub fn try_partition_array<T, const N: usize>(
+    items: impl IntoIterator<Item = T>,
+    bucket_resolver: impl Fn(&T) -> usize,
+) -> Result<[Vec<T>; N]> {
+    const { assert!(N > 0, "N must be greater than 0") };
+
+    let mut buckets: [Vec<T>; N] = std::array::from_fn(|_| Vec::new());
+    fill_buckets(&mut buckets, items, bucket_resolver)?;
+    Ok(buckets)
+}
+
+/// Routes every item into one of the given buckets, stopping at the first item
+/// whose bucket does not exist.
+fn fill_buckets<T>(
+    buckets: &mut [Vec<T>],
+    items: impl IntoIterator<Item = T>,
+    bucket_resolver: impl Fn(&T) -> usize,
+) -> Result<()> {
+    for (index, item) in items.into_iter().enumerate() {
+        let bucket = bucket_resolver(&item);
+        let buckets_len = buckets.len();
+        buckets
+            .get_mut(bucket)
+            .ok_or(Error::BucketOutOfRange {
+                index,
+                bucket,
+                buckets: buckets_len,
+            })?
+            .push(item);
+    }
+    Ok(())
+}
+
+/// A key with a fixed, small set of values that can each be mapped to a bucket,
+/// typically a field-less enum.
+///
+/// Implement this for an enum to use it with [`partition_by_key`]. `bucket` must
+/// return a distinct value in `0..COUNT` for every key.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::PartitionKey;
+///
+/// #[derive(Clone, Copy)]
+/// enum Level {
+///     Info,
+///     Warn,
+///     Error,
+/// }
+///
+/// impl PartitionKey for Level {
+///     const COUNT: usize = 3;
+///
+///     fn bucket(self) -> usize {
+///         self as usize
+///     }
+/// }
+/// ```
+pub trait PartitionKey: Copy {
+    /// The number of distinct keys.
+    const COUNT: usize;
+
+    /// Returns the bucket of this key, in `0..COUNT`.
+    fn bucket(self) -> usize;
+}
+
+impl PartitionKey for bool {
+    const COUNT: usize = 2;
+
+    fn bucket(self) -> usize {
+        usize::from(self)
+    }
+}
+
+/// Splits an iterable into one bucket per value of a [`PartitionKey`], in a
+/// single pass and without hashing.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `key_resolver` - Returns the key of an item
+///
+/// # Returns
+///
+/// A [`Partitions`] holding one bucket per key, which can be indexed by the key
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{PartitionKey, partition_by_key};
+///
+/// #[derive(Clone, Copy)]
+/// enum Sink {
+///     Archive,
+///     Alert,
+/// }
+///
+/// impl PartitionKey for Sink {
+///     const COUNT: usize = 2;
+///
+///     fn bucket(self) -> usize {
+///         self as usize
+///     }
+/// }
+///
+/// let events = vec![("disk", 95), ("cpu", 20), ("mem", 99)];
+/// let mut routed = partition_by_key(events, |&(_, load)| {
+///     if load > 90 { Sink::Alert } else { Sink::Archive }
+/// });
+/// assert_eq!(routed[Sink::Alert], vec![("disk", 95), ("mem", 99)]);
+/// assert_eq!(routed.take(Sink::Archive), vec![("cpu", 20)]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if a key's `bucket` is not less than `K::COUNT`.
+pub fn partition_by_key<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Partitions<K, T>
+where
+    K: PartitionKey,
+{
+    try_partition_by_key(items, key_resolver).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Splits an iterable into one bucket per value of a [`PartitionKey`], returning
+/// an error instead of panicking when a key maps to a missing bucket.
+///
+/// This is the fallible counterpart of [`partition_by_key`], useful when the
+/// [`PartitionKey`] implementation is not trusted to stay within `0..COUNT`.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable to split
+/// * `key_resolver` - Returns the key of an item
+///
+/// # Returns
+///
+/// `Ok` with one bucket per key, or [`Error::BucketOutOfRange`] for the first
+/// item whose key's `bucket` is not less than `K::COUNT`
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{Error, PartitionKey, try_partition_by_key};
+///
+/// #[derive(Clone, Copy)]
+/// struct Digit(usize);
+///
+/// impl PartitionKey for Digit {
+///     const COUNT: usize = 10;
+///
+///     fn bucket(self) -> usize {
+///         self.0
+///     }
+/// }
+///
+/// let routed = try_partition_by_key([3, 14], |&n| Digit(n)).map(|p| p.into_inner().len());
+/// assert_eq!(
+///     routed,
+///     Err(Error::BucketOutOfRange { index: 1, bucket: 14, buckets: 10 })
+/// );
+/// ```
+pub fn try_partition_by_key<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Result<Partitions<K, T>>
+where
+    K: PartitionKey,
+{
+    let mut buckets: Vec<Vec<T>> = (0..K::COUNT).map(|_| Vec::new()).collect();
+    fill_buckets(&mut buckets, items, |item| key_resolver(item).bucket())?;
+    Ok(Partitions {
+        buckets,
+        key: PhantomData,
+    })
+}
+
+/// The buckets produced by [`partition_by_key`] and [`try_partition_by_key`],
+/// one per value of `K`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Partitions<K, T> {
+    buckets: Vec<Vec<T>>,
+    key: PhantomData<fn(K)>,
+}
+
+impl<K: PartitionKey, T> Partitions<K, T> {
+    /// Returns the items routed to `key`, in input order.
+    pub fn get(&self, key: K) -> &[T] {
+        &self.buckets[key.bucket()]
+    }
+
+    /// Moves the items routed to `key` out, leaving that bucket empty.
+    pub fn take(&mut self, key: K) -> Vec<T> {
+        std::mem::take(&mut self.buckets[key.bucket()])
+    }
+
+    /// Returns every bucket, ordered by [`PartitionKey::bucket`].
+    pub fn into_inner(self) -> Vec<Vec<T>> {
+        self.buckets
+    }
+}
+
+impl<K: PartitionKey, T> ops::Index<K> for Partitions<K, T> {
+    type Output = Vec<T>;
+
+    fn index(&self, key: K) -> &Vec<T> {
+        &self.buckets[key.bucket()]
+    }
+}
+
+/// Extension trait that adds N-way partitioning methods to any iterator.
+pub trait PartitionExt: Iterator {
+    /// Splits the iterator into `n` buckets chosen by `bucket_resolver`.
+    ///
+    /// See [`partition_n`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::PartitionExt;
+    ///
+    /// let buckets = ["a", "bb", "cc", "d"].into_iter().partition_n(2, |s| s.len() - 1);
+    /// assert_eq!(buckets, vec![vec!["a", "d"], vec!["bb", "cc"]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0 or if `bucket_resolver` returns a bucket outside `0..n`.
+    fn partition_n(
+        self,
+        n: usize,
+        bucket_resolver: impl Fn(&Self::Item) -> usize,
+    ) -> Vec<Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        partition_n(self, n, bucket_resolver)
+    }
+
+    /// Splits the iterator into `n` buckets, returning an error instead of panicking.
+    ///
+    /// See [`try_partition_n`] for details.
+    fn try_partition_n(
+        self,
+        n: usize,
+        bucket_resolver: impl Fn(&Self::Item) -> usize,
+    ) -> Result<Vec<Vec<Self::Item>>>
+    where
+        Self: Sized,
+    {
+        try_partition_n(self, n, bucket_resolver)
+    }
+
+    /// Splits the iterator into `N` buckets known at compile time.
+    ///
+    /// See [`partition_array`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::PartitionExt;
+    ///
+    /// let [even, odd] = (1..=5).partition_array(|&n| n % 2);
+    /// assert_eq!(even, vec![2, 4]);
+    /// assert_eq!(odd, vec![1, 3, 5]);
+    /// ```
+    fn partition_array<const N: usize>(
+        self,
+        bucket_resolver: impl Fn(&Self::Item) -> usize,
+    ) -> [Vec<Self::Item>; N]
+    where
+        Self: Sized,
+    {
+        partition_array(self, bucket_resolver)
+    }
+
+    /// Splits the iterator into `N` buckets, returning an error instead of panicking.
+    ///
+    /// See [`try_partition_array`] for details.
+    fn try_partition_array<const N: usize>(
+        self,
+        bucket_resolver: impl Fn(&Self::Item) -> usize,
+    ) -> Result<[Vec<Self::Item>; N]>
+    where
+        Self: Sized,
+    {
+        try_partition_array(self, bucket_resolver)
+    }
+
+    /// Splits the iterator into one bucket per value of a [`PartitionKey`].
+    ///
+    /// See [`partition_by_key`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::PartitionExt;
+    ///
+    /// let routed = (1..=4).partition_by_key(|&n| n > 2);
+    /// assert_eq!(routed[false], vec![1, 2]);
+    /// assert_eq!(routed[true], vec![3, 4]);
+    /// ```
+    fn partition_by_key<K>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Partitions<K, Self::Item>
+    where
+        Self: Sized,
+        K: PartitionKey,
+    {
+        partition_by_key(self, key_resolver)
+    }
+
+    /// Splits the iterator into one bucket per value of a [`PartitionKey`],
+    /// returning an error instead of panicking.
+    ///
+    /// See [`try_partition_by_key`] for details.
+    fn try_partition_by_key<K>(
+        self,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Result<Partitions<K, Self::Item>>
+    where
+        Self: Sized,
+        K: PartitionKey,
+    {
+        try_partition_by_key(self, key_resolver)
+    }
+}
+
+impl<I: Iterator> PartitionExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Priority {
+        Low,
+        Normal,
+        High,
+    }
+
+    impl PartitionKey for Priority {
+        const COUNT: usize = 3;
+
+        fn bucket(self) -> usize {
+            self as usize
+        }
+    }
+
+    #[test]
+    fn test_partition_n_keeps_order_and_empty_buckets() {
+        let buckets = partition_n(vec![4, 1, 7, 3], 4, |&n| n % 3);
+        assert_eq!(buckets, vec![vec![3], vec![4, 1, 7], vec![], vec![]]);
+
+        let buckets = partition_n(Vec::<i32>::new(), 2, |_| 0);
+        assert_eq!(buckets, vec![Vec::<i32>::new(), vec![]]);
+    }
+
+    #[test]
+    fn test_try_partition_n_errors() {
+        assert_eq!(try_partition_n(vec![1], 0, |_| 0), Err(Error::ZeroSize));
+        assert_eq!(
+            (0..5).try_partition_n(3, |&n| n),
+            Err(Error::BucketOutOfRange {
+                index: 3,
+                bucket: 3,
+                buckets: 3,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "routed to bucket 5")]
+    fn test_partition_array_panics_on_missing_bucket() {
+        let _: [Vec<usize>; 2] = partition_array(vec![0, 5], |&n| n);
+    }
+
+    #[test]
+    fn test_partition_by_key() {
+        let tasks = vec![
+            ("deploy", Priority::High),
+            ("lint", Priority::Low),
+            ("review", Priority::Normal),
+            ("hotfix", Priority::High),
+        ];
+        let mut routed = tasks
+            .into_iter()
+            .partition_by_key(|&(_, priority)| priority);
+        assert_eq!(routed.get(Priority::Low), &[("lint", Priority::Low)]);
+        assert_eq!(
+            routed.take(Priority::High),
+            vec![("deploy", Priority::High), ("hotfix", Priority::High)]
+        );
+        assert!(routed[Priority::High].is_empty());
+        assert_eq!(routed.into_inner().len(), Priority::COUNT);
+    }
+
+    #[test]
+    fn test_try_partition_by_key_rejects_out_of_range_bucket() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Faulty(usize);
+
+        impl PartitionKey for Faulty {
+            const COUNT: usize = 2;
+
+            fn bucket(self) -> usize {
+                self.0
+            }
+        }
+
+        assert_eq!(
+            (0..4).try_partition_by_key(|&n| Faulty(n)),
+            Err(Error::BucketOutOfRange {
+                index: 2,
+                bucket: 2,
+                buckets: 2,
+            })
+        );
+        let routed = try_partition_by_key([1, 0, 1], |&n| Faulty(n)).unwrap();
+        assert_eq!(routed.into_inner(), vec![vec![0], vec![1, 1]]);
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::{HashSet, VecDeque};
+use std::ops::RangeBounds;
+
 /// Splits an iterable into kept and removed items based on a predicate.
 ///
 /// This function consumes the input iterable and evaluates each item with the
@@ -32,6 +35,100 @@ pub fn remove<T>(
     (kept, removed)
 }
 
+/// Splits an iterable into kept and removed items based on a predicate that also
+/// receives each item's position.
+///
+/// Returns a tuple `(kept, removed)` where both are `Vec<T>`, like [`remove`].
+///
+/// # Arguments
+///
+/// - `items` - An iterable producing items of type `T`
+/// - `should_remove_element` - Predicate receiving the zero-based index and the item
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::remove_with_index;
+///
+/// let items = vec!["a", "b", "c", "d"];
+/// let (kept, removed) = remove_with_index(items, |i, _| i % 2 == 1);
+/// assert_eq!(kept, vec!["a", "c"]);
+/// assert_eq!(removed, vec!["b", "d"]);
+/// ```
+pub fn remove_with_index<T>(
+    items: impl IntoIterator<Item = T>,
+    should_remove_element: impl Fn(usize, &T) -> bool,
+) -> (Vec<T>, Vec<T>) {
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        if should_remove_element(index, &item) {
+            removed.push(item);
+        } else {
+            kept.push(item);
+        }
+    }
+    (kept, removed)
+}
+
+/// Splits an iterable into kept items and the items at the given positions.
+///
+/// The indices may be listed in any order; the removed items are always returned
+/// in index order. Repeated indices remove their item once, and indices past the
+/// end of the input are ignored.
+///
+/// Returns a tuple `(kept, removed)` where both are `Vec<T>`, like [`remove`].
+///
+/// # Arguments
+///
+/// - `items` - An iterable producing items of type `T`
+/// - `indices` - The zero-based positions of the items to remove
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::pull_at;
+///
+/// let items = vec![10, 20, 30, 40, 50];
+/// let (kept, removed) = pull_at(items, &[3, 1]);
+/// assert_eq!(kept, vec![10, 30, 50]);
+/// assert_eq!(removed, vec![20, 40]);
+/// ```
+pub fn pull_at<T>(items: impl IntoIterator<Item = T>, indices: &[usize]) -> (Vec<T>, Vec<T>) {
+    let indices: HashSet<usize> = indices.iter().copied().collect();
+    remove_with_index(items, |index, _| indices.contains(&index))
+}
+
+/// Splits an iterable into kept items and the items whose positions fall within a
+/// range.
+///
+/// Unlike `Vec::drain`, a range reaching past the end of the input does not panic;
+/// only the positions that exist are removed.
+///
+/// Returns a tuple `(kept, removed)` where both are `Vec<T>`, like [`remove`].
+///
+/// # Arguments
+///
+/// - `items` - An iterable producing items of type `T`
+/// - `range` - The zero-based positions of the items to remove
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::remove_range;
+///
+/// let items = vec!['a', 'b', 'c', 'd', 'e'];
+/// let (kept, removed) = remove_range(items, 1..3);
+/// assert_eq!(kept, vec!['a', 'd', 'e']);
+/// assert_eq!(removed, vec!['b', 'c']);
+/// ```
+pub fn remove_range<T>(
+    items: impl IntoIterator<Item = T>,
+    range: impl RangeBounds<usize>,
+) -> (Vec<T>, Vec<T>) {
+    remove_with_index(items, |index, _| range.contains(&index))
+}
+
 /// Extension trait that adds the `remove` method to any iterator.
 pub trait RemoveExt: Iterator {
     /// Splits the iterator items into kept and removed based on a predicate.
@@ -46,10 +143,155 @@ pub trait RemoveExt: Iterator {
     {
         remove(self, should_remove_element)
     }
+
+    /// Splits the iterator items into kept and removed based on a predicate that
+    /// also receives each item's index.
+    ///
+    /// Returns `(kept, removed)`. See [`remove_with_index`].
+    fn remove_with_index(
+        self,
+        should_remove_element: impl Fn(usize, &Self::Item) -> bool,
+    ) -> (Vec<Self::Item>, Vec<Self::Item>)
+    where
+        Self: Sized,
+    {
+        remove_with_index(self, should_remove_element)
+    }
+
+    /// Splits the iterator items into kept items and the items at the given indices.
+    ///
+    /// Returns `(kept, removed)`. See [`pull_at`].
+    fn pull_at(self, indices: &[usize]) -> (Vec<Self::Item>, Vec<Self::Item>)
+    where
+        Self: Sized,
+    {
+        pull_at(self, indices)
+    }
+
+    /// Splits the iterator items into kept items and the items within an index range.
+    ///
+    /// Returns `(kept, removed)`. See [`remove_range`].
+    fn remove_range(self, range: impl RangeBounds<usize>) -> (Vec<Self::Item>, Vec<Self::Item>)
+    where
+        Self: Sized,
+    {
+        remove_range(self, range)
+    }
 }
 
 impl<I: Iterator> RemoveExt for I {}
 
+/// Extension trait that removes matching elements from a `Vec` or `VecDeque` in
+/// place, like es-toolkit's mutating `remove`.
+///
+/// Unlike [`remove`], no new collection is allocated for the kept elements: they
+/// stay in the original container, in their original order. Each call makes a
+/// single O(n) pass over the elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::VecDeque;
+///
+/// use rust_toolkit::VecRemoveExt;
+///
+/// let mut numbers = vec![1, 2, 3, 4, 5];
+/// let removed = numbers.remove_where(|&n| n % 2 == 0);
+/// assert_eq!(numbers, vec![1, 3, 5]);
+/// assert_eq!(removed, vec![2, 4]);
+///
+/// let mut queue: VecDeque<i32> = (1..=5).collect();
+/// let removed: Vec<i32> = queue.drain_where(|&n| n > 3).collect();
+/// assert_eq!(queue, [1, 2, 3]);
+/// assert_eq!(removed, vec![4, 5]);
+/// ```
+pub trait VecRemoveExt<T> {
+    /// Removes the elements for which the predicate returns `true` and returns
+    /// them in their original order.
+    fn remove_where(&mut self, should_remove_element: impl FnMut(&T) -> bool) -> Vec<T>;
+
+    /// Lazily removes the elements for which the predicate returns `true`,
+    /// yielding them in their original order.
+    ///
+    /// If the iterator is dropped before it is exhausted, the elements that were
+    /// not yet visited are kept, and the container stays in its original order.
+    fn drain_where<F>(&mut self, should_remove_element: F) -> impl Iterator<Item = T>
+    where
+        F: FnMut(&T) -> bool;
+}
+
+impl<T> VecRemoveExt<T> for Vec<T> {
+    fn remove_where(&mut self, should_remove_element: impl FnMut(&T) -> bool) -> Vec<T> {
+        self.drain_where(should_remove_element).collect()
+    }
+
+    fn drain_where<F>(&mut self, mut should_remove_element: F) -> impl Iterator<Item = T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.extract_if(.., move |item| should_remove_element(item))
+    }
+}
+
+impl<T> VecRemoveExt<T> for VecDeque<T> {
+    fn remove_where(&mut self, should_remove_element: impl FnMut(&T) -> bool) -> Vec<T> {
+        self.drain_where(should_remove_element).collect()
+    }
+
+    fn drain_where<F>(&mut self, should_remove_element: F) -> impl Iterator<Item = T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        DequeDrainWhere {
+            remaining: self.len(),
+            deque: self,
+            should_remove_element,
+        }
+    }
+}
+
+/// Drains matching elements from a `VecDeque` by rotating the kept elements from
+/// the front to the back, which reuses the existing buffer.
+struct DequeDrainWhere<'a, T, F> {
+    deque: &'a mut VecDeque<T>,
+    /// The number of elements at the front that were not visited yet.
+    remaining: usize,
+    should_remove_element: F,
+}
+
+impl<T, F> Iterator for DequeDrainWhere<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.remaining > 0 {
+            self.remaining -= 1;
+            // Rotate the item to the back before testing it, so it stays in the
+            // deque even if the predicate panics.
+            let item = self.deque.pop_front()?;
+            self.deque.push_back(item);
+            let item = self.deque.back().expect("item was just pushed");
+            if (self.should_remove_element)(item) {
+                return self.deque.pop_back();
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+impl<T, F> Drop for DequeDrainWhere<'_, T, F> {
+    fn drop(&mut self) {
+        // Move the unvisited elements behind the kept ones to restore the order
+        self.deque.rotate_left(self.remaining);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +319,78 @@ mod tests {
         assert!(kept.is_empty());
         assert!(removed.is_empty());
     }
+
+    #[test]
+    fn test_vec_remove_where_in_place() {
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+        let capacity = items.capacity();
+        let removed = items.remove_where(|&n| n % 3 == 0);
+        assert_eq!(items, vec![1, 2, 4, 5]);
+        assert_eq!(removed, vec![3, 6]);
+        assert_eq!(items.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_deque_remove_where_preserves_order() {
+        let mut items: VecDeque<String> = ["a", "bb", "c", "dd"].map(String::from).into();
+        let removed = items.remove_where(|s| s.len() == 2);
+        assert_eq!(items, ["a", "c"]);
+        assert_eq!(removed, vec!["bb", "dd"]);
+    }
+
+    #[test]
+    fn test_drain_where_dropped_early_keeps_rest() {
+        let mut items = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(items.drain_where(|&n| n % 2 == 0).next(), Some(2));
+        assert_eq!(items, vec![1, 3, 4, 5, 6]);
+
+        let mut items: VecDeque<i32> = (1..=6).collect();
+        assert_eq!(items.drain_where(|&n| n % 2 == 0).next(), Some(2));
+        assert_eq!(items, [1, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_remove_with_index() {
+        let items = vec![5, 5, 5, 1];
+        let (kept, removed) = remove_with_index(items, |i, &n| i > 0 && n == 5);
+        assert_eq!(kept, vec![5, 1]);
+        assert_eq!(removed, vec![5, 5]);
+
+        let (kept, removed) = (1..=4).remove_with_index(|i, &n| i == 0 || n == 4);
+        assert_eq!(kept, vec![2, 3]);
+        assert_eq!(removed, vec![1, 4]);
+    }
+
+    #[test]
+    fn test_pull_at_ignores_repeats_and_out_of_range() {
+        let items = vec!["a", "b", "c", "d"];
+        let (kept, removed) = pull_at(items, &[2, 0, 2, 9]);
+        assert_eq!(kept, vec!["b", "d"]);
+        assert_eq!(removed, vec!["a", "c"]);
+
+        let (kept, removed) = vec![1, 2, 3].into_iter().pull_at(&[]);
+        assert_eq!(kept, vec![1, 2, 3]);
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn test_remove_range() {
+        let (kept, removed) = remove_range(0..6, 4..);
+        assert_eq!(kept, vec![0, 1, 2, 3]);
+        assert_eq!(removed, vec![4, 5]);
+
+        let (kept, removed) = (0..3).remove_range(..=10);
+        assert!(kept.is_empty());
+        assert_eq!(removed, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_deque_drain_where_keeps_items_when_predicate_panics() {
+        let mut items: VecDeque<i32> = (1..=5).collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            items.remove_where(|&n| if n == 3 { panic!("boom") } else { n == 1 })
+        }));
+        assert!(result.is_err());
+        assert_eq!(items, [2, 3, 4, 5]);
+    }
 }
@@ -0,0 +1,957 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::iter::Peekable;
+
+use crate::array::uniq::{select, uniq_by, uniq_owned, uniq_with};
+
+/// Returns the items of `items` that do not appear in `values`.
+///
+/// This is the equivalent of es-toolkit's `difference`. The result keeps the
+/// input order of `items`, including repeated items that are not excluded.
+///
+/// # Arguments
+///
+/// - `items` - The iterable to filter
+/// - `values` - The items to exclude
+///
+/// # Returns
+///
+/// A `Vec<T>` of the items not found in `values`, in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::difference;
+///
+/// let result = difference(vec![1, 2, 3, 4, 5], vec![2, 4]);
+/// assert_eq!(result, vec![1, 3, 5]);
+/// ```
+pub fn difference<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    let excluded: HashSet<T> = values.into_iter().collect();
+    items
+        .into_iter()
+        .filter(|item| !excluded.contains(item))
+        .collect()
+}
+
+/// Returns the items of `items` whose derived key does not appear among the keys
+/// of `values`.
+///
+/// This is the equivalent of es-toolkit's `differenceBy`. See [`difference`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::difference_by;
+///
+/// let result = difference_by(vec![1.2, 2.3, 3.4], vec![2.9], |n: &f64| n.floor() as i64);
+/// assert_eq!(result, vec![1.2, 3.4]);
+/// ```
+pub fn difference_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Vec<T>
+where
+    K: Eq + Hash,
+{
+    let excluded: HashSet<K> = values
+        .into_iter()
+        .map(|value| key_resolver(&value))
+        .collect();
+    items
+        .into_iter()
+        .filter(|item| !excluded.contains(&key_resolver(item)))
+        .collect()
+}
+
+/// Returns the items of `items` that are not equal to any item of `values`
+/// according to a custom equality function.
+///
+/// This is the equivalent of es-toolkit's `differenceWith`. Items are compared
+/// pairwise, so this costs O(n·m) comparisons. See [`difference`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::difference_with;
+///
+/// let close = |a: &f64, b: &f64| (a - b).abs() < 0.1;
+/// let result = difference_with(vec![1.0, 2.0, 3.0], vec![2.05], close);
+/// assert_eq!(result, vec![1.0, 3.0]);
+/// ```
+pub fn difference_with<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    is_equal: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    let values: Vec<T> = values.into_iter().collect();
+    items
+        .into_iter()
+        .filter(|item| !values.iter().any(|value| is_equal(item, value)))
+        .collect()
+}
+
+/// Returns the items of `items` that also appear in `values`.
+///
+/// This is the equivalent of es-toolkit's `intersection`. The result keeps the
+/// input order of `items`, including repeated items that are found in `values`.
+///
+/// # Arguments
+///
+/// - `items` - The iterable to filter
+/// - `values` - The items to look for
+///
+/// # Returns
+///
+/// A `Vec<T>` of the items also found in `values`, in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::intersection;
+///
+/// let result = intersection(vec![1, 2, 3, 4, 5], vec![5, 3, 9]);
+/// assert_eq!(result, vec![3, 5]);
+/// ```
+pub fn intersection<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    let included: HashSet<T> = values.into_iter().collect();
+    items
+        .into_iter()
+        .filter(|item| included.contains(item))
+        .collect()
+}
+
+/// Returns the items of `items` whose derived key also appears among the keys of
+/// `values`.
+///
+/// This is the equivalent of es-toolkit's `intersectionBy`. See [`intersection`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::intersection_by;
+///
+/// let result = intersection_by(vec!["apple", "kiwi", "fig"], vec!["pear"], |s| s.len());
+/// assert_eq!(result, vec!["kiwi"]);
+/// ```
+pub fn intersection_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Vec<T>
+where
+    K: Eq + Hash,
+{
+    let included: HashSet<K> = values
+        .into_iter()
+        .map(|value| key_resolver(&value))
+        .collect();
+    items
+        .into_iter()
+        .filter(|item| included.contains(&key_resolver(item)))
+        .collect()
+}
+
+/// Returns the items of `items` that are equal to some item of `values` according
+/// to a custom equality function.
+///
+/// This is the equivalent of es-toolkit's `intersectionWith`. Items are compared
+/// pairwise, so this costs O(n·m) comparisons. See [`intersection`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::intersection_with;
+///
+/// let close = |a: &f64, b: &f64| (a - b).abs() < 0.1;
+/// let result = intersection_with(vec![1.0, 2.0, 3.0], vec![2.05], close);
+/// assert_eq!(result, vec![2.0]);
+/// ```
+pub fn intersection_with<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    is_equal: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    let values: Vec<T> = values.into_iter().collect();
+    items
+        .into_iter()
+        .filter(|item| values.iter().any(|value| is_equal(item, value)))
+        .collect()
+}
+
+/// Returns the distinct items of both iterables, in order of first occurrence.
+///
+/// This is the equivalent of es-toolkit's `union`: the items of `items` come
+/// first, followed by the items of `values` that were not seen yet. Unlike
+/// [`uniq`](crate::uniq), no item is cloned.
+///
+/// # Arguments
+///
+/// - `items` - The first iterable
+/// - `values` - The second iterable
+///
+/// # Returns
+///
+/// A `Vec<T>` holding every distinct item once, in order of first occurrence
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::union;
+///
+/// let result = union(vec![3, 1, 3], vec![2, 1, 4]);
+/// assert_eq!(result, vec![3, 1, 2, 4]);
+/// ```
+pub fn union<T>(items: impl IntoIterator<Item = T>, values: impl IntoIterator<Item = T>) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    uniq_owned(items.into_iter().chain(values).collect())
+}
+
+/// Returns the items of both iterables with distinct derived keys, in order of
+/// first occurrence.
+///
+/// This is the equivalent of es-toolkit's `unionBy`. See [`union`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::union_by;
+///
+/// let result = union_by(vec!["a", "bb"], vec!["cc", "ddd"], |s| s.len());
+/// assert_eq!(result, vec!["a", "bb", "ddd"]);
+/// ```
+pub fn union_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Vec<T>
+where
+    K: Eq + Hash,
+{
+    uniq_by(items.into_iter().chain(values), key_resolver)
+}
+
+/// Returns the items of both iterables that are distinct according to a custom
+/// equality function, in order of first occurrence.
+///
+/// This is the equivalent of es-toolkit's `unionWith`. Items are compared
+/// pairwise, so this costs O(n²) comparisons. See [`union`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::union_with;
+///
+/// let close = |a: &f64, b: &f64| (a - b).abs() < 0.1;
+/// let result = union_with(vec![1.0, 2.0], vec![2.05, 3.0], close);
+/// assert_eq!(result, vec![1.0, 2.0, 3.0]);
+/// ```
+pub fn union_with<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    is_equal: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    uniq_with(items.into_iter().chain(values), is_equal)
+}
+
+/// Returns the distinct items that appear in exactly one of the two iterables.
+///
+/// This is the equivalent of es-toolkit's `xor`: the items only found in `items`
+/// come first, followed by the items only found in `values`, each once and in
+/// order of first occurrence.
+///
+/// # Arguments
+///
+/// - `items` - The first iterable
+/// - `values` - The second iterable
+///
+/// # Returns
+///
+/// A `Vec<T>` of the symmetric difference of both iterables
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::xor;
+///
+/// let result = xor(vec![1, 2, 3, 1], vec![3, 4, 4]);
+/// assert_eq!(result, vec![1, 2, 4]);
+/// ```
+pub fn xor<T>(items: impl IntoIterator<Item = T>, values: impl IntoIterator<Item = T>) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    let items = uniq_owned(items.into_iter().collect());
+    let values = uniq_owned(values.into_iter().collect());
+
+    let in_items: HashSet<&T> = items.iter().collect();
+    let in_values: HashSet<&T> = values.iter().collect();
+    let keep_items: Vec<bool> = items.iter().map(|item| !in_values.contains(item)).collect();
+    let keep_values: Vec<bool> = values
+        .iter()
+        .map(|value| !in_items.contains(value))
+        .collect();
+
+    select(items, keep_items)
+        .chain(select(values, keep_values))
+        .collect()
+}
+
+/// Returns the items with a derived key that appears in exactly one of the two
+/// iterables.
+///
+/// This is the equivalent of es-toolkit's `xorBy`. See [`xor`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::xor_by;
+///
+/// let result = xor_by(vec![1.2, 2.3], vec![2.9, 3.1], |n: &f64| n.floor() as i64);
+/// assert_eq!(result, vec![1.2, 3.1]);
+/// ```
+pub fn xor_by<T, K>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> Vec<T>
+where
+    K: Eq + Hash,
+{
+    let items: Vec<T> = items.into_iter().collect();
+    let values: Vec<T> = values.into_iter().collect();
+
+    let item_keys: HashSet<K> = items.iter().map(&key_resolver).collect();
+    let value_keys: HashSet<K> = values.iter().map(&key_resolver).collect();
+
+    let only_items = items
+        .into_iter()
+        .filter(|item| !value_keys.contains(&key_resolver(item)));
+    let only_values = values
+        .into_iter()
+        .filter(|value| !item_keys.contains(&key_resolver(value)));
+    uniq_by(only_items.chain(only_values), &key_resolver)
+}
+
+/// Returns the items that are equal to no item of the other iterable according to
+/// a custom equality function.
+///
+/// This is the equivalent of es-toolkit's `xorWith`. Items are compared pairwise,
+/// so this costs O(n²) comparisons. See [`xor`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::xor_with;
+///
+/// let close = |a: &f64, b: &f64| (a - b).abs() < 0.1;
+/// let result = xor_with(vec![1.0, 2.0], vec![2.05, 3.0], close);
+/// assert_eq!(result, vec![1.0, 3.0]);
+/// ```
+pub fn xor_with<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+    is_equal: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    let items = uniq_with(items, &is_equal);
+    let values = uniq_with(values, &is_equal);
+
+    let keep_items: Vec<bool> = items
+        .iter()
+        .map(|item| !values.iter().any(|value| is_equal(item, value)))
+        .collect();
+    let keep_values: Vec<bool> = values
+        .iter()
+        .map(|value| !items.iter().any(|item| is_equal(item, value)))
+        .collect();
+
+    select(items, keep_items)
+        .chain(select(values, keep_values))
+        .collect()
+}
+
+/// Returns the items of a sorted iterable that do not appear in another sorted
+/// iterable, using a linear merge instead of hashing.
+///
+/// Both inputs must be sorted in ascending order; otherwise the result is
+/// unspecified. For sorted inputs, the result equals that of [`difference`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::difference_sorted;
+///
+/// let result = difference_sorted(vec![1, 2, 2, 3, 5], vec![2, 4, 5]);
+/// assert_eq!(result, vec![1, 3]);
+/// ```
+pub fn difference_sorted<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Ord,
+{
+    let mut values = values.into_iter().peekable();
+    items
+        .into_iter()
+        .filter(|item| !advance_to(&mut values, item))
+        .collect()
+}
+
+/// Returns the items of a sorted iterable that also appear in another sorted
+/// iterable, using a linear merge instead of hashing.
+///
+/// Both inputs must be sorted in ascending order; otherwise the result is
+/// unspecified. For sorted inputs, the result equals that of [`intersection`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::intersection_sorted;
+///
+/// let result = intersection_sorted(vec![1, 2, 2, 3, 5], vec![2, 4, 5]);
+/// assert_eq!(result, vec![2, 2, 5]);
+/// ```
+pub fn intersection_sorted<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Ord,
+{
+    let mut values = values.into_iter().peekable();
+    items
+        .into_iter()
+        .filter(|item| advance_to(&mut values, item))
+        .collect()
+}
+
+/// Returns the distinct items of two sorted iterables as one sorted `Vec`, using a
+/// linear merge instead of hashing.
+///
+/// Both inputs must be sorted in ascending order; otherwise the result is
+/// unspecified. For sorted inputs, the result holds the same items as [`union`],
+/// but stays sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::union_sorted;
+///
+/// let result = union_sorted(vec![1, 3, 3, 5], vec![2, 3, 6]);
+/// assert_eq!(result, vec![1, 2, 3, 5, 6]);
+/// ```
+pub fn union_sorted<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Ord,
+{
+    let mut items = items.into_iter().peekable();
+    let mut values = values.into_iter().peekable();
+    let mut result: Vec<T> = Vec::new();
+
+    loop {
+        let next = match (items.peek(), values.peek()) {
+            (Some(item), Some(value)) if value < item => values.next(),
+            (Some(_), _) => items.next(),
+            (None, Some(_)) => values.next(),
+            (None, None) => break,
+        };
+        let next = next.expect("peeked an item");
+        if result.last() != Some(&next) {
+            result.push(next);
+        }
+    }
+
+    result
+}
+
+/// Returns the distinct items that appear in exactly one of two sorted iterables
+/// as one sorted `Vec`, using a linear merge instead of hashing.
+///
+/// Both inputs must be sorted in ascending order; otherwise the result is
+/// unspecified. For sorted inputs, the result holds the same items as [`xor`],
+/// but stays sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::xor_sorted;
+///
+/// let result = xor_sorted(vec![1, 1, 2, 3], vec![3, 4, 4]);
+/// assert_eq!(result, vec![1, 2, 4]);
+/// ```
+pub fn xor_sorted<T>(
+    items: impl IntoIterator<Item = T>,
+    values: impl IntoIterator<Item = T>,
+) -> Vec<T>
+where
+    T: Ord,
+{
+    let mut items = items.into_iter().peekable();
+    let mut values = values.into_iter().peekable();
+    let mut result: Vec<T> = Vec::new();
+
+    loop {
+        let next = match (items.peek(), values.peek()) {
+            (Some(item), Some(value)) if item == value => {
+                // Present on both sides: skip every copy of it
+                let shared = items.next().expect("peeked an item");
+                while items.next_if_eq(&shared).is_some() {}
+                while values.next_if_eq(&shared).is_some() {}
+                continue;
+            }
+            (Some(item), Some(value)) if value < item => values.next(),
+            (Some(_), _) => items.next(),
+            (None, Some(_)) => values.next(),
+            (None, None) => break,
+        };
+        let next = next.expect("peeked an item");
+        if result.last() != Some(&next) {
+            result.push(next);
+        }
+    }
+
+    result
+}
+
+/// Skips the items of a sorted iterator that are less than `target` and reports
+/// whether the next remaining item equals it.
+fn advance_to<T: Ord>(values: &mut Peekable<impl Iterator<Item = T>>, target: &T) -> bool {
+    while values.next_if(|value| value < target).is_some() {}
+    values.peek() == Some(target)
+}
+
+/// Extension trait that adds set-style operations to any iterator.
+///
+/// The iterator plays the role of `items` in the matching free functions.
+pub trait SetOpsExt: Iterator {
+    /// Returns the items that do not appear in `values`. See [`difference`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=5).difference([2, 4]), vec![1, 3, 5]);
+    /// ```
+    fn difference(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        difference(self, values)
+    }
+
+    /// Returns the items whose key does not appear among the keys of `values`.
+    /// See [`difference_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!(["a", "bb", "cc"].into_iter().difference_by(["xx"], |s| s.len()), vec!["a"]);
+    /// ```
+    fn difference_by<K>(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        difference_by(self, values, key_resolver)
+    }
+
+    /// Returns the items equal to no item of `values` according to `is_equal`.
+    /// See [`difference_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let same_parity = |a: &i32, b: &i32| a % 2 == b % 2;
+    /// assert_eq!((1..=4).difference_with([2], same_parity), vec![1, 3]);
+    /// ```
+    fn difference_with(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        is_equal: impl Fn(&Self::Item, &Self::Item) -> bool,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        difference_with(self, values, is_equal)
+    }
+
+    /// Returns the items not in `values`, merging two sorted inputs.
+    /// See [`difference_sorted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=5).difference_sorted([2, 4]), vec![1, 3, 5]);
+    /// ```
+    fn difference_sorted(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        difference_sorted(self, values)
+    }
+
+    /// Returns the items that also appear in `values`. See [`intersection`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=5).intersection([5, 3, 9]), vec![3, 5]);
+    /// ```
+    fn intersection(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        intersection(self, values)
+    }
+
+    /// Returns the items whose key also appears among the keys of `values`.
+    /// See [`intersection_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let result = ["a", "bb", "cc"].into_iter().intersection_by(["xx"], |s| s.len());
+    /// assert_eq!(result, vec!["bb", "cc"]);
+    /// ```
+    fn intersection_by<K>(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        intersection_by(self, values, key_resolver)
+    }
+
+    /// Returns the items equal to some item of `values` according to `is_equal`.
+    /// See [`intersection_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let same_parity = |a: &i32, b: &i32| a % 2 == b % 2;
+    /// assert_eq!((1..=4).intersection_with([2], same_parity), vec![2, 4]);
+    /// ```
+    fn intersection_with(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        is_equal: impl Fn(&Self::Item, &Self::Item) -> bool,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        intersection_with(self, values, is_equal)
+    }
+
+    /// Returns the items also in `values`, merging two sorted inputs.
+    /// See [`intersection_sorted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=5).intersection_sorted([3, 5, 9]), vec![3, 5]);
+    /// ```
+    fn intersection_sorted(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        intersection_sorted(self, values)
+    }
+
+    /// Returns the distinct items of the iterator and `values`. See [`union`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!([3, 1, 3].into_iter().union([2, 1]), vec![3, 1, 2]);
+    /// ```
+    fn union(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        union(self, values)
+    }
+
+    /// Returns the items of the iterator and `values` with distinct keys.
+    /// See [`union_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let result = ["a", "bb"].into_iter().union_by(["cc", "ddd"], |s| s.len());
+    /// assert_eq!(result, vec!["a", "bb", "ddd"]);
+    /// ```
+    fn union_by<K>(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        union_by(self, values, key_resolver)
+    }
+
+    /// Returns the items of the iterator and `values` that are distinct according
+    /// to `is_equal`. See [`union_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let same_parity = |a: &i32, b: &i32| a % 2 == b % 2;
+    /// assert_eq!((1..=4).union_with([6], same_parity), vec![1, 2]);
+    /// ```
+    fn union_with(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        is_equal: impl Fn(&Self::Item, &Self::Item) -> bool,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        union_with(self, values, is_equal)
+    }
+
+    /// Returns the distinct items of both sorted inputs as one sorted `Vec`.
+    /// See [`union_sorted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!([1, 3, 3].into_iter().union_sorted([2, 3]), vec![1, 2, 3]);
+    /// ```
+    fn union_sorted(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        union_sorted(self, values)
+    }
+
+    /// Returns the distinct items found in exactly one of the iterator and
+    /// `values`. See [`xor`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=3).xor([3, 4]), vec![1, 2, 4]);
+    /// ```
+    fn xor(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        xor(self, values)
+    }
+
+    /// Returns the items with a key found in exactly one of the iterator and
+    /// `values`. See [`xor_by`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!(["a", "bb"].into_iter().xor_by(["cc", "ddd"], |s| s.len()), vec!["a", "ddd"]);
+    /// ```
+    fn xor_by<K>(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        key_resolver: impl Fn(&Self::Item) -> K,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        xor_by(self, values, key_resolver)
+    }
+
+    /// Returns the items equal to no item of the other side according to
+    /// `is_equal`. See [`xor_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// let same_parity = |a: &i32, b: &i32| a % 2 == b % 2;
+    /// assert_eq!([1, 3].into_iter().xor_with([2, 5], same_parity), vec![2]);
+    /// ```
+    fn xor_with(
+        self,
+        values: impl IntoIterator<Item = Self::Item>,
+        is_equal: impl Fn(&Self::Item, &Self::Item) -> bool,
+    ) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        xor_with(self, values, is_equal)
+    }
+
+    /// Returns the distinct items found in exactly one of two sorted inputs as one
+    /// sorted `Vec`. See [`xor_sorted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::SetOpsExt;
+    ///
+    /// assert_eq!((1..=3).xor_sorted([3, 4]), vec![1, 2, 4]);
+    /// ```
+    fn xor_sorted(self, values: impl IntoIterator<Item = Self::Item>) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        xor_sorted(self, values)
+    }
+}
+
+impl<I: Iterator> SetOpsExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Token(String);
+
+    fn tokens(words: &[&str]) -> Vec<Token> {
+        words.iter().map(|word| Token(word.to_string())).collect()
+    }
+
+    #[test]
+    fn test_difference_and_intersection_keep_repeats() {
+        let items = vec![1, 2, 1, 3, 2];
+        assert_eq!(difference(items.clone(), vec![2]), vec![1, 1, 3]);
+        assert_eq!(intersection(items, vec![2, 3]), vec![2, 3, 2]);
+    }
+
+    #[test]
+    fn test_union_and_xor_do_not_clone() {
+        let result = union(tokens(&["b", "a", "b"]), tokens(&["c", "a"]));
+        assert_eq!(result, tokens(&["b", "a", "c"]));
+
+        let result = tokens(&["b", "a", "b"])
+            .into_iter()
+            .xor(tokens(&["c", "a", "c"]));
+        assert_eq!(result, tokens(&["b", "c"]));
+    }
+
+    #[test]
+    fn test_by_variants() {
+        let len = |s: &&str| s.len();
+        let items = vec!["a", "bb", "cc", "ddd"];
+        assert_eq!(
+            difference_by(items.clone(), vec!["xx"], len),
+            vec!["a", "ddd"]
+        );
+        assert_eq!(
+            intersection_by(items.clone(), vec!["xx"], len),
+            vec!["bb", "cc"]
+        );
+        assert_eq!(
+            union_by(items.clone(), vec!["eeee"], len),
+            vec!["a", "bb", "ddd", "eeee"]
+        );
+        assert_eq!(
+            xor_by(items, vec!["x", "yyyy"], len),
+            vec!["bb", "ddd", "yyyy"]
+        );
+    }
+
+    #[test]
+    fn test_with_variants() {
+        let same_parity = |a: &i32, b: &i32| a % 2 == b % 2;
+        assert_eq!(difference_with(1..=4, [2], same_parity), vec![1, 3]);
+        assert_eq!(intersection_with(1..=4, [2], same_parity), vec![2, 4]);
+        assert_eq!((1..=4).union_with([6], same_parity), vec![1, 2]);
+        assert_eq!(xor_with([1, 3], [2, 5], same_parity), vec![2]);
+    }
+
+    #[test]
+    fn test_sorted_variants_match_hashed() {
+        let items = vec![1, 2, 2, 4, 6, 7, 9];
+        let values = vec![0, 2, 3, 6, 6, 10];
+
+        assert_eq!(
+            difference_sorted(items.clone(), values.clone()),
+            difference(items.clone(), values.clone())
+        );
+        assert_eq!(
+            intersection_sorted(items.clone(), values.clone()),
+            intersection(items.clone(), values.clone())
+        );
+
+        let mut expected = union(items.clone(), values.clone());
+        expected.sort();
+        assert_eq!(union_sorted(items.clone(), values.clone()), expected);
+
+        let mut expected = xor(items.clone(), values.clone());
+        expected.sort();
+        assert_eq!(items.into_iter().xor_sorted(values), expected);
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        assert_eq!(difference(Vec::<i32>::new(), vec![1]), Vec::<i32>::new());
+        assert_eq!(union_sorted(Vec::<i32>::new(), vec![1, 1]), vec![1]);
+        assert_eq!(xor_sorted(vec![1], Vec::<i32>::new()), vec![1]);
+    }
+}
@@ -0,0 +1,637 @@
+use std::hash::{Hash, Hasher};
+
+use crate::error::{Error, Result};
+
+/// The smallest precision accepted by [`HyperLogLog::new`].
+pub const MIN_PRECISION: u8 = 4;
+/// The largest precision accepted by [`HyperLogLog::new`].
+pub const MAX_PRECISION: u8 = 16;
+/// The precision used by [`approx_uniq_count`], giving a standard error of about 0.8%.
+pub const DEFAULT_PRECISION: u8 = 14;
+
+const COUNT_MIN_MAGIC: &[u8; 4] = b"CMS1";
+const HYPER_LOG_LOG_MAGIC: &[u8; 4] = b"HLL1";
+
+/// A 64-bit FNV-1a hasher with a final avalanche step.
+///
+/// Unlike `DefaultHasher`, it is unseeded, so sketches built on different shards
+/// or in different processes agree. Integers are always hashed in little-endian
+/// order, so keys made of integers and byte slices hash identically on every
+/// platform and Rust release. Other keys, such as `str` or types with a derived
+/// `Hash`, depend on how std feeds them to the hasher, which is not guaranteed to
+/// stay the same across Rust releases.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        // MurmurHash3's fmix64, so that every input bit affects every output bit.
+        let mut hash = self.0;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        hash ^ (hash >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+}
+
+fn stable_hash<K: Hash + ?Sized>(key: &K) -> u64 {
+    let mut hasher = StableHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Reads little-endian integers from a byte slice, failing on truncated input.
+struct ByteReader<'a>(&'a [u8]);
+
+impl ByteReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let (head, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or(Error::InvalidSketchBytes)?;
+        self.0 = rest;
+        Ok(*head)
+    }
+}
+
+/// A Count-Min Sketch that estimates how many times each key was seen in bounded
+/// memory.
+///
+/// The sketch keeps `depth` rows of `width` counters. Estimates never undercount;
+/// with `width = ⌈e / ε⌉` and `depth = ⌈ln(1 / δ)⌉` an estimate exceeds the true
+/// count by more than `ε · total` with probability at most `δ`.
+///
+/// Sketches built with the same `width` and `depth` can be merged, e.g. to combine
+/// shards, and serialized with [`CountMinSketch::to_bytes`].
+///
+/// # Binary format
+///
+/// All integers are little-endian:
+///
+/// | Bytes | Content |
+/// |-------|---------|
+/// | 4 | magic `b"CMS1"` |
+/// | 4 | `width` as `u32` |
+/// | 4 | `depth` as `u32` |
+/// | 8 | total count as `u64` |
+/// | 8 × width × depth | counters as `u64`, row by row |
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::CountMinSketch;
+///
+/// let mut sketch = CountMinSketch::new(256, 4).unwrap();
+/// sketch.add(&"apple");
+/// sketch.add(&"apple");
+/// sketch.add(&"banana");
+/// assert!(sketch.estimate(&"apple") >= 2);
+/// assert_eq!(sketch.total(), 3);
+///
+/// let restored = CountMinSketch::from_bytes(&sketch.to_bytes()).unwrap();
+/// assert_eq!(restored, sketch);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    total: u64,
+    counters: Vec<u64>,
+}
+
+impl CountMinSketch {
+    /// Creates an empty sketch with `depth` rows of `width` counters.
+    ///
+    /// Returns [`Error::ZeroSize`] if `width` or `depth` is 0, or
+    /// [`Error::SketchTooLarge`] if they do not fit in the `u32` fields of the
+    /// binary format or the counters would not fit in memory.
+    pub fn new(width: usize, depth: usize) -> Result<Self> {
+        if width == 0 || depth == 0 {
+            return Err(Error::ZeroSize);
+        }
+        let len = width
+            .checked_mul(depth)
+            .filter(|len| len.checked_mul(8).is_some())
+            .filter(|_| u32::try_from(width).is_ok() && u32::try_from(depth).is_ok())
+            .ok_or(Error::SketchTooLarge { width, depth })?;
+        Ok(CountMinSketch {
+            width,
+            depth,
+            total: 0,
+            counters: vec![0; len],
+        })
+    }
+
+    /// Returns the number of counters per row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the total of all counts added to the sketch.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Records one occurrence of `key`.
+    pub fn add<K: Hash + ?Sized>(&mut self, key: &K) {
+        self.add_count(key, 1);
+    }
+
+    /// Records `count` occurrences of `key`.
+    pub fn add_count<K: Hash + ?Sized>(&mut self, key: &K, count: u64) {
+        let hash = stable_hash(key);
+        for row in 0..self.depth {
+            let slot = self.slot(hash, row);
+            self.counters[slot] = self.counters[slot].saturating_add(count);
+        }
+        self.total = self.total.saturating_add(count);
+    }
+
+    /// Returns the estimated number of occurrences of `key`, which is never lower
+    /// than the true count.
+    pub fn estimate<K: Hash + ?Sized>(&self, key: &K) -> u64 {
+        let hash = stable_hash(key);
+        (0..self.depth)
+            .map(|row| self.counters[self.slot(hash, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Adds the counts of `other` into this sketch.
+    ///
+    /// Returns [`Error::SketchMismatch`] if the sketches have different dimensions.
+    pub fn merge(&mut self, other: &CountMinSketch) -> Result<()> {
+        if self.width != other.width || self.depth != other.depth {
+            return Err(Error::SketchMismatch);
+        }
+        for (counter, other) in self.counters.iter_mut().zip(&other.counters) {
+            *counter = counter.saturating_add(*other);
+        }
+        self.total = self.total.saturating_add(other.total);
+        Ok(())
+    }
+
+    /// Serializes the sketch into the binary format described on [`CountMinSketch`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(20 + 8 * self.counters.len());
+        bytes.extend_from_slice(COUNT_MIN_MAGIC);
+        bytes.extend_from_slice(&(self.width as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.depth as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.total.to_le_bytes());
+        for counter in &self.counters {
+            bytes.extend_from_slice(&counter.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a sketch produced by [`CountMinSketch::to_bytes`].
+    ///
+    /// Returns [`Error::InvalidSketchBytes`] if the bytes are not a valid sketch.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader(bytes);
+        if &reader.take::<4>()? != COUNT_MIN_MAGIC {
+            return Err(Error::InvalidSketchBytes);
+        }
+        let width = u32::from_le_bytes(reader.take()?) as usize;
+        let depth = u32::from_le_bytes(reader.take()?) as usize;
+        let total = u64::from_le_bytes(reader.take()?);
+
+        // Check the header against the payload before allocating anything
+        let payload_len = width.checked_mul(depth).and_then(|len| len.checked_mul(8));
+        if payload_len != Some(reader.0.len()) {
+            return Err(Error::InvalidSketchBytes);
+        }
+        let mut sketch =
+            CountMinSketch::new(width, depth).map_err(|_| Error::InvalidSketchBytes)?;
+        for counter in &mut sketch.counters {
+            *counter = u64::from_le_bytes(reader.take()?);
+        }
+        sketch.total = total;
+        Ok(sketch)
+    }
+
+    /// Derives the counter for `row` from one 64-bit hash by double hashing.
+    fn slot(&self, hash: u64, row: usize) -> usize {
+        let (low, high) = (hash as u32 as u64, hash >> 32);
+        let column = low.wrapping_add((row as u64).wrapping_mul(high)) % self.width as u64;
+        row * self.width + column as usize
+    }
+}
+
+/// A HyperLogLog estimator of the number of distinct keys seen, in bounded memory.
+///
+/// A sketch with precision `p` uses `2^p` one-byte registers and has a standard
+/// error of about `1.04 / √(2^p)`. Sketches with the same precision can be merged,
+/// e.g. to combine shards, and serialized with [`HyperLogLog::to_bytes`].
+///
+/// # Binary format
+///
+/// | Bytes | Content |
+/// |-------|---------|
+/// | 4 | magic `b"HLL1"` |
+/// | 1 | precision `p` as `u8` |
+/// | 2^p | registers as `u8` |
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::HyperLogLog;
+///
+/// let mut left = HyperLogLog::new(12).unwrap();
+/// let mut right = HyperLogLog::new(12).unwrap();
+/// (0..1000).for_each(|n| left.add(&n));
+/// (500..1500).for_each(|n| right.add(&n));
+///
+/// left.merge(&right).unwrap();
+/// let estimate = left.count();
+/// assert!((1400..=1600).contains(&estimate));
+///
+/// let restored = HyperLogLog::from_bytes(&left.to_bytes()).unwrap();
+/// assert_eq!(restored.count(), estimate);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// Creates an empty estimator with `2^precision` registers.
+    ///
+    /// Returns [`Error::InvalidPrecision`] unless `precision` is between
+    /// [`MIN_PRECISION`] and [`MAX_PRECISION`].
+    pub fn new(precision: u8) -> Result<Self> {
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            return Err(Error::InvalidPrecision {
+                precision,
+                min: MIN_PRECISION,
+                max: MAX_PRECISION,
+            });
+        }
+        Ok(HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+        })
+    }
+
+    /// Returns the precision the estimator was created with.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Records `key`.
+    pub fn add<K: Hash + ?Sized>(&mut self, key: &K) {
+        let hash = stable_hash(key);
+        let register = (hash >> (64 - self.precision)) as usize;
+        // The position of the first set bit among the remaining bits, capped for an all-zero tail.
+        let rank = ((hash << self.precision).leading_zeros() + 1)
+            .min(65 - u32::from(self.precision)) as u8;
+        self.registers[register] = self.registers[register].max(rank);
+    }
+
+    /// Returns the estimated number of distinct keys recorded.
+    pub fn count(&self) -> u64 {
+        let registers = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / registers),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let estimate = alpha * registers * registers / sum;
+
+        let empty = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if estimate <= 2.5 * registers && empty > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            (registers * (registers / empty as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+
+    /// Merges the keys recorded by `other` into this estimator.
+    ///
+    /// Returns [`Error::SketchMismatch`] if the estimators have different precisions.
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<()> {
+        if self.precision != other.precision {
+            return Err(Error::SketchMismatch);
+        }
+        for (register, &other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other);
+        }
+        Ok(())
+    }
+
+    /// Serializes the estimator into the binary format described on [`HyperLogLog`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5 + self.registers.len());
+        bytes.extend_from_slice(HYPER_LOG_LOG_MAGIC);
+        bytes.push(self.precision);
+        bytes.extend_from_slice(&self.registers);
+        bytes
+    }
+
+    /// Deserializes an estimator produced by [`HyperLogLog::to_bytes`].
+    ///
+    /// Returns [`Error::InvalidSketchBytes`] if the bytes are not a valid estimator.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader(bytes);
+        if &reader.take::<4>()? != HYPER_LOG_LOG_MAGIC {
+            return Err(Error::InvalidSketchBytes);
+        }
+        let [precision] = reader.take::<1>()?;
+        let mut hll = HyperLogLog::new(precision).map_err(|_| Error::InvalidSketchBytes)?;
+
+        let max_rank = 65 - precision;
+        if reader.0.len() != hll.registers.len() || reader.0.iter().any(|&rank| rank > max_rank) {
+            return Err(Error::InvalidSketchBytes);
+        }
+        hll.registers.copy_from_slice(reader.0);
+        Ok(hll)
+    }
+}
+
+/// Builds a [`CountMinSketch`] from an iterable collection, counting the key derived
+/// from each item by a resolver function.
+///
+/// This is the bounded-memory, approximate counterpart of [`count_by`](crate::count_by).
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `width` - The number of counters per row
+/// * `depth` - The number of rows
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// `Ok` with the sketch, [`Error::ZeroSize`] if `width` or `depth` is 0, or
+/// [`Error::SketchTooLarge`] if they exceed the supported size
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::count_min_sketch;
+///
+/// let log = vec!["GET /", "GET /a", "GET /", "POST /b"];
+/// let sketch = count_min_sketch(log, 64, 4, |line| line.split(' ').next().unwrap().to_string()).unwrap();
+/// assert!(sketch.estimate("GET") >= 3);
+/// ```
+pub fn count_min_sketch<T, K>(
+    items: impl IntoIterator<Item = T>,
+    width: usize,
+    depth: usize,
+    key_resolver: impl Fn(&T) -> K,
+) -> Result<CountMinSketch>
+where
+    K: Hash,
+{
+    let mut sketch = CountMinSketch::new(width, depth)?;
+    for item in items {
+        sketch.add(&key_resolver(&item));
+    }
+    Ok(sketch)
+}
+
+/// Builds a [`HyperLogLog`] with the given precision from an iterable collection,
+/// recording the key derived from each item by a resolver function.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `precision` - The precision of the estimator
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// `Ok` with the estimator, or [`Error::InvalidPrecision`] if `precision` is out of range
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::hyper_log_log;
+///
+/// let shard = hyper_log_log(0..100, 10, |&n| n % 10).unwrap();
+/// assert_eq!(shard.count(), 10);
+/// ```
+pub fn hyper_log_log<T, K>(
+    items: impl IntoIterator<Item = T>,
+    precision: u8,
+    key_resolver: impl Fn(&T) -> K,
+) -> Result<HyperLogLog>
+where
+    K: Hash,
+{
+    let mut hll = HyperLogLog::new(precision)?;
+    for item in items {
+        hll.add(&key_resolver(&item));
+    }
+    Ok(hll)
+}
+
+/// Estimates the number of distinct keys derived from an iterable collection by a
+/// resolver function, using a [`HyperLogLog`] with [`DEFAULT_PRECISION`].
+///
+/// This is the bounded-memory, approximate counterpart of `uniq(..).len()`. Use
+/// [`hyper_log_log`] instead to keep the estimator for merging or serialization.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// The estimated number of distinct keys
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::approx_uniq_count;
+///
+/// let estimate = approx_uniq_count(0..10_000, |&n| n / 2);
+/// assert!((4_900..=5_100).contains(&estimate));
+/// ```
+pub fn approx_uniq_count<T, K>(
+    items: impl IntoIterator<Item = T>,
+    key_resolver: impl Fn(&T) -> K,
+) -> u64
+where
+    K: Hash,
+{
+    hyper_log_log(items, DEFAULT_PRECISION, key_resolver)
+        .expect("DEFAULT_PRECISION is a valid precision")
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_by, uniq};
+
+    #[test]
+    fn test_stable_hash_is_fixed() {
+        // Guards the serialized format: these values must never change. Only
+        // integer and byte writes are pinned, as `str` hashing may change with std.
+        assert_eq!(stable_hash(&42u32), 0xb8ac_a8f2_54d1_6bd2);
+        let mut hasher = StableHasher::new();
+        hasher.write(b"apple");
+        assert_eq!(hasher.finish(), 0x9bd6_c11a_2c6b_f096);
+        assert_eq!(stable_hash(&1usize), stable_hash(&1u64));
+    }
+
+    #[test]
+    fn test_count_min_sketch_never_undercounts() {
+        let items: Vec<u32> = (0..5_000).map(|n| n % 97 * (n % 7)).collect();
+        let exact = count_by(items.clone(), |&n| n);
+        let sketch = count_min_sketch(items, 128, 5, |&n| n).unwrap();
+
+        let total = sketch.total();
+        assert_eq!(total, 5_000);
+        for (key, &count) in &exact {
+            let estimate = sketch.estimate(key);
+            assert!(estimate >= count as u64);
+            // ε = e / width, so large overestimates should be rare.
+            assert!(
+                estimate - count as u64 <= total / 16,
+                "{key}: {estimate} vs {count}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_min_sketch_merge_and_bytes() {
+        let left = count_min_sketch(vec!["a", "b", "a"], 32, 3, |&s| s).unwrap();
+        let right = count_min_sketch(vec!["a", "c"], 32, 3, |&s| s).unwrap();
+        let mut merged = left.clone();
+        merged.merge(&right).unwrap();
+        assert_eq!(merged.estimate("a"), 3);
+        assert_eq!(merged.total(), 5);
+
+        let bytes = merged.to_bytes();
+        assert_eq!(&bytes[..4], b"CMS1");
+        assert_eq!(bytes.len(), 20 + 8 * 32 * 3);
+        assert_eq!(CountMinSketch::from_bytes(&bytes), Ok(merged));
+
+        let other = CountMinSketch::new(16, 3).unwrap();
+        assert_eq!(left.clone().merge(&other), Err(Error::SketchMismatch));
+        assert_eq!(
+            CountMinSketch::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::InvalidSketchBytes)
+        );
+        assert_eq!(CountMinSketch::new(0, 3), Err(Error::ZeroSize));
+        assert_eq!(
+            CountMinSketch::new(usize::MAX, 2),
+            Err(Error::SketchTooLarge {
+                width: usize::MAX,
+                depth: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_count_min_sketch_rejects_oversized_header() {
+        let mut bytes = b"CMS1".to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        assert_eq!(
+            CountMinSketch::from_bytes(&bytes),
+            Err(Error::InvalidSketchBytes)
+        );
+    }
+
+    #[test]
+    fn test_approx_uniq_count_close_to_uniq() {
+        let items: Vec<String> = (0..20_000)
+            .map(|n| format!("user-{}", n % 12_345))
+            .collect();
+        let exact = uniq(items.clone()).len() as f64;
+        let estimate = approx_uniq_count(items, |s| s.clone()) as f64;
+        assert!(
+            (estimate - exact).abs() / exact < 0.03,
+            "{estimate} vs {exact}"
+        );
+    }
+
+    #[test]
+    fn test_hyper_log_log_small_counts_are_exact() {
+        let hll = hyper_log_log(vec![1, 2, 3, 2, 1], 12, |&n| n).unwrap();
+        assert_eq!(hll.count(), 3);
+        assert_eq!(HyperLogLog::new(12).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_hyper_log_log_merge_and_bytes() {
+        let left = hyper_log_log(0..3_000, 12, |&n| n).unwrap();
+        let right = hyper_log_log(2_000..5_000, 12, |&n| n).unwrap();
+        let mut merged = left.clone();
+        merged.merge(&right).unwrap();
+        let estimate = merged.count() as f64;
+        assert!((estimate - 5_000.0).abs() / 5_000.0 < 0.05, "{estimate}");
+
+        let bytes = merged.to_bytes();
+        assert_eq!(&bytes[..5], b"HLL1\x0c");
+        assert_eq!(HyperLogLog::from_bytes(&bytes), Ok(merged));
+
+        assert_eq!(
+            left.clone().merge(&HyperLogLog::new(10).unwrap()),
+            Err(Error::SketchMismatch)
+        );
+        assert_eq!(
+            HyperLogLog::new(3),
+            Err(Error::InvalidPrecision {
+                precision: 3,
+                min: MIN_PRECISION,
+                max: MAX_PRECISION,
+            })
+        );
+        assert_eq!(
+            HyperLogLog::from_bytes(b"HLL1"),
+            Err(Error::InvalidSketchBytes)
+        );
+    }
+}
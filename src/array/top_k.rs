@@ -0,0 +1,178 @@
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+
+/// A key reported by [`count_by_top_k`] together with its estimated count.
+///
+/// The true number of items that produced `key` lies between
+/// [`HeavyHitter::min_count`] (`count - error`) and `count`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeavyHitter<K> {
+    /// The key.
+    pub key: K,
+    /// The estimated count, which never underestimates the true count.
+    pub count: usize,
+    /// The maximum amount by which `count` may overestimate the true count.
+    pub error: usize,
+}
+
+impl<K> HeavyHitter<K> {
+    /// Returns the guaranteed lower bound of the true count.
+    pub fn min_count(&self) -> usize {
+        self.count - self.error
+    }
+}
+
+/// Approximately counts the most frequent keys of an iterable collection using at
+/// most `k` counters.
+///
+/// This implements the Space-Saving algorithm: while fewer than `k` keys are
+/// tracked every new key gets its own counter, and afterwards a new key replaces
+/// the key with the smallest counter, inheriting its count as the error bound.
+/// Memory use is O(k) no matter how many distinct keys the input has, which makes
+/// it suitable for high-cardinality streams where [`count_by`](crate::count_by)
+/// would exhaust memory.
+///
+/// Every key that produced more than `n / k` of the `n` items is guaranteed to be
+/// reported, and each reported count overestimates the true count by at most its
+/// `error`.
+///
+/// # Arguments
+///
+/// * `items` - An iterable collection of items of type `T`
+/// * `k` - The maximum number of keys to track
+/// * `key_resolver` - A function that takes a reference to an item and returns a key of type `K`
+///
+/// # Returns
+///
+/// Up to `k` [`HeavyHitter`]s sorted by estimated count, highest first
+///
+/// # Type Parameters
+///
+/// * `T` - The type of items in the input collection
+/// * `K` - The type of keys (must implement `Hash + Eq + Clone`)
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::count_by_top_k;
+///
+/// let log = vec!["GET /", "GET /a", "GET /", "POST /b", "GET /", "GET /a"];
+/// let top = count_by_top_k(log, 2, |line| *line);
+/// assert_eq!(top[0].key, "GET /");
+/// assert!(top[0].min_count() <= 3 && 3 <= top[0].count);
+/// ```
+pub fn count_by_top_k<T, K>(
+    items: impl IntoIterator<Item = T>,
+    k: usize,
+    key_resolver: impl Fn(&T) -> K,
+) -> Vec<HeavyHitter<K>>
+where
+    K: Hash + Eq + Clone,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut counters: Vec<HeavyHitter<K>> = Vec::with_capacity(k);
+    // Maps each tracked key to its position in `counters`
+    let mut positions: HashMap<K, usize> = HashMap::with_capacity(k);
+    // Orders the counters by count so the smallest one can be replaced in O(log k)
+    let mut by_count: BTreeSet<(usize, usize)> = BTreeSet::new();
+
+    for item in items {
+        let key = key_resolver(&item);
+
+        if let Some(&position) = positions.get(&key) {
+            let counter = &mut counters[position];
+            by_count.remove(&(counter.count, position));
+            counter.count += 1;
+            by_count.insert((counter.count, position));
+        } else if counters.len() < k {
+            let position = counters.len();
+            positions.insert(key.clone(), position);
+            counters.push(HeavyHitter {
+                key,
+                count: 1,
+                error: 0,
+            });
+            by_count.insert((1, position));
+        } else {
+            let (min_count, position) = by_count.pop_first().expect("k counters are tracked");
+            let counter = &mut counters[position];
+            positions.remove(&counter.key);
+            positions.insert(key.clone(), position);
+            *counter = HeavyHitter {
+                key,
+                count: min_count + 1,
+                error: min_count,
+            };
+            by_count.insert((counter.count, position));
+        }
+    }
+
+    counters.sort_by(|a, b| b.count.cmp(&a.count).then(a.error.cmp(&b.error)));
+    counters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CountByExt, count_by};
+
+    /// A deterministic skewed stream: key `i` appears roughly `1000 / (i + 1)` times.
+    fn skewed_stream() -> Vec<u32> {
+        let mut items = Vec::new();
+        for round in 0..1000u32 {
+            for key in 0..50u32 {
+                if round % (key + 1) == 0 {
+                    items.push(key);
+                }
+            }
+        }
+        items
+    }
+
+    #[test]
+    fn test_count_by_top_k_exact_when_k_covers_all_keys() {
+        let items = vec!["a", "b", "a", "c", "a", "b"];
+        let top = count_by_top_k(items.clone(), 3, |&s| s);
+        let exact = count_by(items, |&s| s);
+        assert_eq!(top.len(), 3);
+        for hitter in &top {
+            assert_eq!(hitter.error, 0);
+            assert_eq!(hitter.count, exact[&hitter.key]);
+        }
+        assert_eq!(top[0].key, "a");
+    }
+
+    #[test]
+    fn test_count_by_top_k_bounds_against_exact_counts() {
+        let items = skewed_stream();
+        let n = items.len();
+        let k = 10;
+        let exact = count_by(items.clone(), |&key| key);
+        let top = items.into_iter().count_by_top_k(k, |&key| key);
+
+        assert_eq!(top.len(), k);
+        for hitter in &top {
+            let true_count = exact[&hitter.key];
+            assert!(
+                hitter.min_count() <= true_count,
+                "{hitter:?} vs {true_count}"
+            );
+            assert!(true_count <= hitter.count, "{hitter:?} vs {true_count}");
+        }
+        for (key, &true_count) in &exact {
+            if true_count > n / k {
+                assert!(top.iter().any(|hitter| hitter.key == *key), "missing {key}");
+            }
+        }
+        assert!(top.windows(2).all(|pair| pair[0].count >= pair[1].count));
+    }
+
+    #[test]
+    fn test_count_by_top_k_zero_and_empty() {
+        assert!(count_by_top_k(vec![1, 2, 3], 0, |&n| n).is_empty());
+        assert!(count_by_top_k(Vec::<i32>::new(), 5, |&n| n).is_empty());
+    }
+}
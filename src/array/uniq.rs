@@ -1,5 +1,8 @@
-use std::collections::HashSet;
-use std::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::iter::{Fuse, FusedIterator};
+
+use crate::array::sketch::approx_uniq_count;
 
 /// Removes duplicate items from an iterable while preserving the order of
 /// first occurrence. Returns a `Vec` containing only the first occurrence of
@@ -60,6 +63,459 @@ where
     result
 }
 
+/// Removes items whose derived key was already seen, preserving the order of first
+/// occurrence.
+///
+/// This is the equivalent of es-toolkit's `uniqBy`: two items are duplicates when
+/// `key_resolver` returns equal keys for them, and only the first is kept. Unlike
+/// [`uniq`], the items themselves need not be hashable or cloneable.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+/// - `key_resolver` - A function that takes a reference to an item and returns its key
+///
+/// # Returns
+///
+/// A `Vec<T>` holding the first item for each distinct key, in input order
+///
+/// # Type Parameters
+///
+/// - `T` - The item type
+/// - `K` - The key type. Must implement `Eq + Hash`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::uniq_by;
+///
+/// let numbers: Vec<f64> = vec![2.1, 1.2, 2.3, 3.4, 1.5];
+/// let result = uniq_by(numbers, |n| n.floor() as i64);
+/// assert_eq!(result, vec![2.1, 1.2, 3.4]);
+/// ```
+pub fn uniq_by<T, K>(items: impl IntoIterator<Item = T>, key_resolver: impl Fn(&T) -> K) -> Vec<T>
+where
+    K: Eq + Hash,
+{
+    let mut seen: HashSet<K> = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(key_resolver(item)))
+        .collect()
+}
+
+/// Removes items that are equal to an earlier item according to a custom equality
+/// function, preserving the order of first occurrence.
+///
+/// This is the equivalent of es-toolkit's `uniqWith`. Since the items are only
+/// compared pairwise, it works for types that cannot be hashed such as floats, at
+/// the cost of O(n²) comparisons.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+/// - `is_equal` - A function that returns `true` when two items are duplicates
+///
+/// # Returns
+///
+/// A `Vec<T>` with duplicates removed while preserving order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::uniq_with;
+///
+/// let readings = vec![1.0, 1.05, 2.0, 2.01, 3.5];
+/// let result = uniq_with(readings, |a: &f64, b: &f64| (a - b).abs() < 0.1);
+/// assert_eq!(result, vec![1.0, 2.0, 3.5]);
+/// ```
+pub fn uniq_with<T>(
+    items: impl IntoIterator<Item = T>,
+    is_equal: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    let mut result: Vec<T> = Vec::new();
+
+    for item in items.into_iter() {
+        if !result.iter().any(|kept| is_equal(kept, &item)) {
+            result.push(item);
+        }
+    }
+
+    result
+}
+
+/// Lazily yields the items of an iterable that were not seen before, preserving the
+/// order of first occurrence.
+///
+/// Unlike [`uniq`], items are neither cloned nor collected: each item is yielded
+/// as soon as it is read, and only a 128-bit fingerprint of it is remembered. The
+/// fingerprint joins two 64-bit hashes of the item, computed with one randomly
+/// keyed `RandomState` and told apart by a distinct leading tag. Since the
+/// yielded items are not kept, a match is never verified against the earlier
+/// item.
+///
+/// The result is therefore probabilistic: two distinct items whose fingerprints
+/// collide are treated as duplicates, and the later one is dropped. Assuming the
+/// keyed hash behaves like a random function, this happens for `n` distinct items
+/// with probability at most about `n² / 2¹²⁹`, which is below `10⁻²⁰` for a
+/// billion items. Use [`uniq`] or [`unique_refs`] where an exact result is
+/// required.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+///
+/// # Returns
+///
+/// A [`Unique`] iterator over the first occurrence of each item
+///
+/// # Type Parameters
+///
+/// - `T` - The item type. Must implement `Hash`; neither `Eq` nor `Clone` is required.
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::unique;
+///
+/// let lines = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+/// let result: Vec<String> = unique(lines).collect();
+/// assert_eq!(result, vec!["a", "b"]);
+/// ```
+pub fn unique<I>(items: I) -> Unique<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Hash,
+{
+    Unique {
+        iter: items.into_iter(),
+        state: RandomState::new(),
+        seen: HashSet::new(),
+    }
+}
+
+/// Lazily yields the references of an iterable of references that were not seen
+/// before, preserving the order of first occurrence.
+///
+/// The references themselves are remembered, so no item is cloned and duplicates
+/// are detected exactly. This is the natural choice for slice input.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces references `&T`, such as `&[T]` or `&Vec<T>`
+///
+/// # Returns
+///
+/// A [`UniqueRefs`] iterator over the first occurrence of each item
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::unique_refs;
+///
+/// let words = vec!["x".to_string(), "y".to_string(), "x".to_string()];
+/// let result: Vec<&String> = unique_refs(&words).collect();
+/// assert_eq!(result, vec!["x", "y"]);
+/// ```
+pub fn unique_refs<'a, T, I>(items: I) -> UniqueRefs<'a, T, I::IntoIter>
+where
+    T: Eq + Hash + ?Sized + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    UniqueRefs {
+        iter: items.into_iter(),
+        seen: HashSet::new(),
+    }
+}
+
+/// An iterator that yields the first occurrence of each item, remembering only
+/// fingerprints.
+///
+/// This struct is created by [`unique`] or [`UniqExt::unique`].
+#[derive(Debug, Clone)]
+pub struct Unique<I> {
+    iter: I,
+    /// The keyed hasher behind both halves of each 128-bit fingerprint.
+    state: RandomState,
+    seen: HashSet<u128>,
+}
+
+impl<I> Iterator for Unique<I>
+where
+    I: Iterator,
+    I::Item: Hash,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = &self.state;
+        let seen = &mut self.seen;
+        self.iter.find(|item| {
+            let high = state.hash_one((0u8, item));
+            let low = state.hash_one((1u8, item));
+            seen.insert((u128::from(high) << 64) | u128::from(low))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        // The first remaining item is always new when nothing has been seen yet.
+        (usize::from(lower > 0 && self.seen.is_empty()), upper)
+    }
+}
+
+impl<I> FusedIterator for Unique<I>
+where
+    I: FusedIterator,
+    I::Item: Hash,
+{
+}
+
+/// An iterator that yields the first occurrence of each referenced item.
+///
+/// This struct is created by [`unique_refs`] or [`UniqExt::unique_refs`].
+#[derive(Debug, Clone)]
+pub struct UniqueRefs<'a, T: ?Sized, I> {
+    iter: I,
+    seen: HashSet<&'a T>,
+}
+
+impl<'a, T, I> Iterator for UniqueRefs<'a, T, I>
+where
+    T: Eq + Hash + ?Sized + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let seen = &mut self.seen;
+        self.iter.find(|&item| seen.insert(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (usize::from(lower > 0 && self.seen.is_empty()), upper)
+    }
+}
+
+impl<'a, T, I> FusedIterator for UniqueRefs<'a, T, I>
+where
+    T: Eq + Hash + ?Sized + 'a,
+    I: FusedIterator<Item = &'a T>,
+{
+}
+
+/// Removes duplicate items from an iterable, keeping the last occurrence of each
+/// item instead of the first.
+///
+/// Surviving items keep their relative input order. No item is cloned.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+///
+/// # Returns
+///
+/// A `Vec<T>` holding the last occurrence of each unique item, in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::uniq_last;
+///
+/// let items = vec![1, 2, 3, 1, 2];
+/// assert_eq!(uniq_last(items), vec![3, 1, 2]);
+/// ```
+pub fn uniq_last<T>(items: impl IntoIterator<Item = T>) -> Vec<T>
+where
+    T: Eq + Hash,
+{
+    let items: Vec<T> = items.into_iter().collect();
+
+    // Walk backwards so the first time an item is seen is its last occurrence
+    let mut seen: HashSet<&T> = HashSet::with_capacity(items.len());
+    let mut keep: Vec<bool> = vec![false; items.len()];
+    for (index, item) in items.iter().enumerate().rev() {
+        keep[index] = seen.insert(item);
+    }
+
+    select(items, keep).collect()
+}
+
+/// Keeps the first occurrence of each item, like [`uniq`] but without cloning.
+///
+/// This backs the set operations, which already own a collected `Vec`.
+pub(crate) fn uniq_owned<T: Eq + Hash>(items: Vec<T>) -> Vec<T> {
+    let mut seen: HashSet<&T> = HashSet::with_capacity(items.len());
+    let keep: Vec<bool> = items.iter().map(|item| seen.insert(item)).collect();
+    select(items, keep).collect()
+}
+
+/// Yields the items whose matching flag is `true`.
+pub(crate) fn select<T>(items: Vec<T>, keep: Vec<bool>) -> impl Iterator<Item = T> {
+    items
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(item, keep)| keep.then_some(item))
+}
+
+/// Lazily removes consecutive duplicate items from an iterable, like `Vec::dedup`
+/// but for any iterator.
+///
+/// Only runs of equal neighbours are collapsed to their first item; equal items
+/// that are not adjacent are all kept. Items are compared with `PartialEq` and
+/// never cloned.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+///
+/// # Returns
+///
+/// A [`Dedup`] iterator over the first item of each run
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::dedup;
+///
+/// let items = vec![1, 1, 2, 2, 2, 1, 3, 3];
+/// let result: Vec<i32> = dedup(items).collect();
+/// assert_eq!(result, vec![1, 2, 1, 3]);
+/// ```
+pub fn dedup<I>(items: I) -> Dedup<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: PartialEq,
+{
+    Dedup {
+        iter: items.into_iter().fuse(),
+        pending: None,
+    }
+}
+
+/// Lazily yields every item that occurs more than once in an iterable, once each,
+/// at the point its second occurrence is read.
+///
+/// Items are compared exactly and never cloned: the first occurrence of each item,
+/// which is never yielded, is kept to compare later items against. Memory therefore
+/// grows with the number of distinct items.
+///
+/// # Arguments
+///
+/// - `items` - An iterable that produces items of type `T`
+///
+/// # Returns
+///
+/// A [`Duplicates`] iterator over the repeated items
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::duplicates;
+///
+/// let items = vec![1, 2, 3, 2, 1, 2];
+/// let result: Vec<i32> = duplicates(items).collect();
+/// assert_eq!(result, vec![2, 1]);
+/// ```
+pub fn duplicates<I>(items: I) -> Duplicates<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash,
+{
+    Duplicates {
+        iter: items.into_iter(),
+        reported: HashMap::new(),
+    }
+}
+
+/// An iterator that collapses runs of equal neighbours to their first item.
+///
+/// This struct is created by [`dedup`] or [`UniqExt::dedup`].
+#[derive(Debug, Clone)]
+pub struct Dedup<I: Iterator> {
+    iter: Fuse<I>,
+    /// The first item of the next run.
+    pending: Option<I::Item>,
+}
+
+impl<I> Iterator for Dedup<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.pending.take().or_else(|| self.iter.next())?;
+        self.pending = self.iter.by_ref().find(|item| *item != current);
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.iter.size_hint();
+        (
+            usize::from(pending > 0 || lower > 0),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<I> FusedIterator for Dedup<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+}
+
+/// An iterator that yields each repeated item once.
+///
+/// This struct is created by [`duplicates`] or [`UniqExt::duplicates`].
+#[derive(Debug, Clone)]
+pub struct Duplicates<I: Iterator> {
+    iter: I,
+    /// Maps the first occurrence of every item to whether it was already yielded.
+    reported: HashMap<I::Item, bool>,
+}
+
+impl<I> Iterator for Duplicates<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reported = &mut self.reported;
+        loop {
+            let item = self.iter.next()?;
+            match reported.get_mut(&item) {
+                // Seen before: yield only if this is the second occurrence
+                Some(yielded) => {
+                    if !std::mem::replace(yielded, true) {
+                        return Some(item);
+                    }
+                }
+                None => {
+                    reported.insert(item, false);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I> FusedIterator for Duplicates<I>
+where
+    I: FusedIterator,
+    I::Item: Eq + Hash,
+{
+}
+
 /// Extension trait that adds the `uniq` method to any iterator.
 ///
 /// This trait provides a convenient `uniq` method so you can call it directly
@@ -108,6 +564,174 @@ pub trait UniqExt: Iterator {
     {
         uniq(self)
     }
+
+    /// Collects the items of the iterator whose derived key was not seen before,
+    /// preserving the order of first occurrence.
+    ///
+    /// See [`uniq_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let words = vec!["apple", "Apple", "banana", "APPLE"];
+    /// let result = words.into_iter().uniq_by(|w| w.to_lowercase());
+    /// assert_eq!(result, vec!["apple", "banana"]);
+    /// ```
+    fn uniq_by<K>(self, key_resolver: impl Fn(&Self::Item) -> K) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        K: Eq + Hash,
+    {
+        uniq_by(self, key_resolver)
+    }
+
+    /// Collects the items of the iterator that are not equal to an earlier item
+    /// according to a custom equality function.
+    ///
+    /// See [`uniq_with`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let points: Vec<(f64, f64)> = vec![(0.0, 0.0), (0.0, 0.01), (1.0, 1.0)];
+    /// let result = points
+    ///     .into_iter()
+    ///     .uniq_with(|a, b| (a.0 - b.0).hypot(a.1 - b.1) < 0.1);
+    /// assert_eq!(result, vec![(0.0, 0.0), (1.0, 1.0)]);
+    /// ```
+    fn uniq_with(self, is_equal: impl Fn(&Self::Item, &Self::Item) -> bool) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        uniq_with(self, is_equal)
+    }
+
+    /// Lazily yields the items that were not seen before, without cloning them.
+    ///
+    /// See [`unique`] for details, including its probabilistic contract.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let first_two: Vec<i32> = vec![3, 3, 1, 3, 2].into_iter().unique().take(2).collect();
+    /// assert_eq!(first_two, vec![3, 1]);
+    /// ```
+    fn unique(self) -> Unique<Self>
+    where
+        Self: Sized,
+        Self::Item: Hash,
+    {
+        unique(self)
+    }
+
+    /// Lazily yields the references that were not seen before, comparing the
+    /// referenced items exactly.
+    ///
+    /// See [`unique_refs`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let words = ["a", "b", "a"];
+    /// let result: Vec<&&str> = words.iter().unique_refs().collect();
+    /// assert_eq!(result, vec![&"a", &"b"]);
+    /// ```
+    fn unique_refs<'a, T>(self) -> UniqueRefs<'a, T, Self>
+    where
+        Self: Sized + Iterator<Item = &'a T>,
+        T: Eq + Hash + ?Sized + 'a,
+    {
+        unique_refs(self)
+    }
+
+    /// Removes duplicate items from the iterator, keeping the last occurrence of each.
+    ///
+    /// See [`uniq_last`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let result = vec!["a", "b", "a"].into_iter().uniq_last();
+    /// assert_eq!(result, vec!["b", "a"]);
+    /// ```
+    fn uniq_last(self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        uniq_last(self)
+    }
+
+    /// Lazily collapses runs of equal neighbours to their first item.
+    ///
+    /// See [`dedup`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let result: String = "aaabccd".chars().dedup().collect();
+    /// assert_eq!(result, "abcd");
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        dedup(self)
+    }
+
+    /// Lazily yields each item that occurs more than once, at its second occurrence.
+    ///
+    /// See [`duplicates`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let result: Vec<char> = "banana".chars().duplicates().collect();
+    /// assert_eq!(result, vec!['a', 'n']);
+    /// ```
+    fn duplicates(self) -> Duplicates<Self>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        duplicates(self)
+    }
+
+    /// Estimates the number of distinct keys derived from the iterator items using a
+    /// HyperLogLog, without storing the items.
+    ///
+    /// See [`approx_uniq_count`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::UniqExt;
+    ///
+    /// let estimate = (0..1_000).approx_uniq_count(|&n| n % 100);
+    /// assert!((98..=102).contains(&estimate));
+    /// ```
+    fn approx_uniq_count<K>(self, key_resolver: impl Fn(&Self::Item) -> K) -> u64
+    where
+        Self: Sized,
+        K: Hash,
+    {
+        approx_uniq_count(self, key_resolver)
+    }
 }
 
 impl<I: Iterator> UniqExt for I {}
@@ -129,4 +753,175 @@ mod tests {
         let result = items.into_iter().uniq();
         assert_eq!(result, vec![1, 2, 3, 4, 5]);
     }
+
+    #[derive(Debug, PartialEq)]
+    struct Reading {
+        sensor: &'static str,
+        value: f64,
+    }
+
+    #[test]
+    fn test_uniq_by() {
+        let readings = vec![
+            Reading {
+                sensor: "a",
+                value: 1.0,
+            },
+            Reading {
+                sensor: "b",
+                value: 2.0,
+            },
+            Reading {
+                sensor: "a",
+                value: 3.0,
+            },
+        ];
+        let result = uniq_by(readings, |reading| reading.sensor);
+        assert_eq!(
+            result,
+            vec![
+                Reading {
+                    sensor: "a",
+                    value: 1.0
+                },
+                Reading {
+                    sensor: "b",
+                    value: 2.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uniq_by_ext() {
+        let items = vec![1, 2, 3, 4, 5, 6];
+        let result = items.into_iter().uniq_by(|&n| n % 3);
+        assert_eq!(result, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_uniq_with() {
+        let items: Vec<f64> = vec![0.1 + 0.2, 0.3, 0.5];
+        let result = uniq_with(items, |a, b| (a - b).abs() < f64::EPSILON);
+        assert_eq!(result, vec![0.1 + 0.2, 0.5]);
+    }
+
+    #[test]
+    fn test_uniq_with_ext_matches_uniq_for_eq() {
+        let items = vec![1, 2, 3, 4, 5, 1, 2, 3];
+        let result = items.clone().into_iter().uniq_with(|a, b| a == b);
+        assert_eq!(result, uniq(items));
+    }
+
+    /// A non-`Clone` type, to check that the lazy adapters never need to clone.
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct Token(String);
+
+    #[test]
+    fn test_unique_is_lazy_and_clone_free() {
+        let tokens = vec![
+            Token("a".to_string()),
+            Token("b".to_string()),
+            Token("a".to_string()),
+        ];
+        let result: Vec<Token> = unique(tokens).collect();
+        assert_eq!(result, vec![Token("a".to_string()), Token("b".to_string())]);
+
+        let pulled = std::cell::Cell::new(0);
+        let mut iter = (0..)
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .map(|n| n / 2)
+            .unique();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn test_unique_matches_uniq() {
+        let items = vec![5, 1, 5, 2, 1, 3, 2];
+        let result: Vec<i32> = items.clone().into_iter().unique().collect();
+        assert_eq!(result, uniq(items));
+    }
+
+    #[test]
+    fn test_unique_refs_borrows_slice() {
+        let tokens = [
+            Token("x".to_string()),
+            Token("y".to_string()),
+            Token("x".to_string()),
+        ];
+        let result: Vec<&Token> = unique_refs(&tokens).collect();
+        assert_eq!(result, vec![&tokens[0], &tokens[1]]);
+
+        let words = ["b", "a", "b"];
+        let result: Vec<&&str> = words.iter().unique_refs().collect();
+        assert_eq!(result, vec![&"b", &"a"]);
+    }
+
+    #[test]
+    fn test_uniq_last() {
+        let items = vec![1, 2, 3, 1, 2, 4];
+        assert_eq!(uniq_last(items), vec![3, 1, 2, 4]);
+
+        let tokens = vec![
+            Token("a".to_string()),
+            Token("b".to_string()),
+            Token("a".to_string()),
+        ];
+        let result = tokens.into_iter().uniq_last();
+        assert_eq!(result, vec![Token("b".to_string()), Token("a".to_string())]);
+
+        assert!(uniq_last(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_dedup() {
+        let items = vec![1, 1, 2, 3, 3, 3, 1, 1];
+        let result: Vec<i32> = dedup(items).collect();
+        assert_eq!(result, vec![1, 2, 3, 1]);
+
+        let tokens = vec![
+            Token("a".to_string()),
+            Token("a".to_string()),
+            Token("b".to_string()),
+        ];
+        let result: Vec<Token> = tokens.into_iter().dedup().collect();
+        assert_eq!(result, vec![Token("a".to_string()), Token("b".to_string())]);
+
+        assert_eq!(dedup(Vec::<i32>::new()).next(), None);
+    }
+
+    #[test]
+    fn test_dedup_is_lazy() {
+        let mut iter = (0..).map(|n| n / 3).dedup();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let items = vec![1, 2, 3, 2, 1, 2, 2, 4];
+        let result: Vec<i32> = duplicates(items).collect();
+        assert_eq!(result, vec![2, 1]);
+
+        let result: Vec<i32> = vec![1, 2, 3].into_iter().duplicates().collect();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_compares_items_exactly() {
+        // Every value hashes the same, so only equality can tell them apart
+        #[derive(Debug, PartialEq, Eq)]
+        struct Collide(u8);
+
+        impl Hash for Collide {
+            fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+        }
+
+        let items = vec![Collide(1), Collide(2), Collide(3), Collide(2)];
+        let result: Vec<Collide> = duplicates(items).collect();
+        assert_eq!(result, vec![Collide(2)]);
+    }
 }
@@ -0,0 +1,361 @@
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use crate::error::{Error, Result};
+
+/// Lazily yields windows of `size` consecutive items from any iterable, starting a
+/// new window every `step` items.
+///
+/// Unlike `slice::windows`, this works on any iterator: items are buffered in a
+/// ring buffer and cloned into each yielded window. With `step < size` windows
+/// overlap, with `step == size` they are disjoint, and with `step > size` the
+/// items between windows are skipped.
+///
+/// By default only full windows are yielded. Call [`Windows::partial`] to also
+/// yield the shorter trailing windows that start before the end of the input.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable
+/// * `size` - The number of items in each window (must be greater than 0)
+/// * `step` - The distance between the starts of two windows (must be greater than 0)
+///
+/// # Returns
+///
+/// A [`Windows`] iterator yielding `Vec<T>` windows in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::windows;
+///
+/// let overlapping: Vec<Vec<i32>> = windows(1..=5, 3, 1).collect();
+/// assert_eq!(overlapping, vec![vec![1, 2, 3], vec![2, 3, 4], vec![3, 4, 5]]);
+///
+/// let strided: Vec<Vec<i32>> = windows(1..=7, 2, 3).collect();
+/// assert_eq!(strided, vec![vec![1, 2], vec![4, 5]]);
+///
+/// let partial: Vec<Vec<i32>> = windows(1..=5, 3, 2).partial().collect();
+/// assert_eq!(partial, vec![vec![1, 2, 3], vec![3, 4, 5], vec![5]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `size` or `step` is 0.
+pub fn windows<I>(items: I, size: usize, step: usize) -> Windows<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    try_windows(items, size, step).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Lazily yields windows of `size` consecutive items every `step` items, returning
+/// an error instead of panicking when `size` or `step` is 0.
+///
+/// # Arguments
+///
+/// * `items` - The input iterable
+/// * `size` - The number of items in each window
+/// * `step` - The distance between the starts of two windows
+///
+/// # Returns
+///
+/// `Ok` with a [`Windows`] iterator, [`Error::ZeroSize`] if `size` is 0, or
+/// [`Error::ZeroStep`] if `step` is 0
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::{Error, try_windows};
+///
+/// assert!(try_windows(1..=3, 2, 1).is_ok());
+/// assert!(matches!(try_windows(1..=3, 2, 0), Err(Error::ZeroStep)));
+/// ```
+pub fn try_windows<I>(items: I, size: usize, step: usize) -> Result<Windows<I::IntoIter>>
+where
+    I: IntoIterator,
+    I::Item: Clone,
+{
+    if size == 0 {
+        return Err(Error::ZeroSize);
+    }
+    if step == 0 {
+        return Err(Error::ZeroStep);
+    }
+
+    Ok(Windows {
+        iter: items.into_iter(),
+        buffer: VecDeque::with_capacity(size),
+        size,
+        step,
+        skip: 0,
+        partial: false,
+        exhausted: false,
+    })
+}
+
+/// An iterator over possibly overlapping windows of an iterator's items.
+///
+/// This struct is created by [`windows`], [`try_windows`] or [`WindowsExt::windows`].
+#[derive(Debug, Clone)]
+pub struct Windows<I: Iterator> {
+    iter: I,
+    buffer: VecDeque<I::Item>,
+    size: usize,
+    step: usize,
+    /// Items still to be discarded before the next window starts (when `step > size`).
+    skip: usize,
+    partial: bool,
+    exhausted: bool,
+}
+
+impl<I: Iterator> Windows<I> {
+    /// Also yields the trailing windows shorter than `size` that start before the
+    /// end of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::WindowsExt;
+    ///
+    /// let windows: Vec<Vec<i32>> = (1..=4).windows(3, 1).partial().collect();
+    /// assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4], vec![3, 4], vec![4]]);
+    /// ```
+    pub fn partial(mut self) -> Self {
+        self.partial = true;
+        self
+    }
+
+    fn fill(&mut self) {
+        while !self.exhausted && self.buffer.len() < self.size {
+            match self.iter.next() {
+                Some(_) if self.skip > 0 => self.skip -= 1,
+                Some(item) => self.buffer.push_back(item),
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        let dropped = self.step.min(self.buffer.len());
+        self.buffer.drain(..dropped);
+        self.skip = self.step - dropped;
+    }
+
+    /// Returns the number of items left for windows, or `None` if it overflows.
+    fn available(&self, remaining: usize) -> Option<usize> {
+        self.buffer
+            .len()
+            .checked_add(remaining.saturating_sub(self.skip))
+    }
+
+    fn count_windows(&self, available: usize) -> usize {
+        if self.partial {
+            available.div_ceil(self.step)
+        } else if available >= self.size {
+            (available - self.size) / self.step + 1
+        } else {
+            0
+        }
+    }
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fill();
+
+        let is_full = self.buffer.len() == self.size;
+        let is_partial = self.partial && !self.buffer.is_empty();
+        if !is_full && !is_partial {
+            return None;
+        }
+
+        let window: Vec<I::Item> = self.buffer.iter().cloned().collect();
+        self.advance();
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            let remaining = self.count_windows(self.buffer.len());
+            return (remaining, Some(remaining));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+        // An overflowing lower bound saturates; an overflowing upper bound is unknown
+        let lower = self.available(lower).unwrap_or(usize::MAX);
+        (
+            self.count_windows(lower),
+            upper
+                .and_then(|upper| self.available(upper))
+                .map(|available| self.count_windows(available)),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for Windows<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+impl<I> FusedIterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+/// Extension trait that adds the `windows` method to any iterator.
+///
+/// This trait provides a convenient way to iterate over overlapping or strided
+/// windows of an iterator without first collecting it into a slice.
+pub trait WindowsExt: Iterator {
+    /// Lazily yields windows of `size` consecutive items, starting a new window
+    /// every `step` items.
+    ///
+    /// See [`windows`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of items in each window (must be greater than 0)
+    /// * `step` - The distance between the starts of two windows (must be greater than 0)
+    ///
+    /// # Returns
+    ///
+    /// A [`Windows`] iterator yielding `Vec<Self::Item>` windows in input order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::WindowsExt;
+    ///
+    /// let sums: Vec<i32> = (1..=5).windows(2, 1).map(|w| w.iter().sum()).collect();
+    /// assert_eq!(sums, vec![3, 5, 7, 9]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `step` is 0.
+    fn windows(self, size: usize, step: usize) -> Windows<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        windows(self, size, step)
+    }
+
+    /// Lazily yields windows of `size` consecutive items every `step` items,
+    /// returning an error instead of panicking when `size` or `step` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of items in each window
+    /// * `step` - The distance between the starts of two windows
+    ///
+    /// # Returns
+    ///
+    /// `Ok` with a [`Windows`] iterator, or an [`Error`] describing the invalid argument.
+    fn try_windows(self, size: usize, step: usize) -> Result<Windows<Self>>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        try_windows(self, size, step)
+    }
+}
+
+/// Blanket implementation of `WindowsExt` for all iterator types.
+impl<I: Iterator> WindowsExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_overlapping() {
+        let result: Vec<Vec<i32>> = windows(vec![1, 2, 3, 4], 2, 1).collect();
+        assert_eq!(result, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_windows_ext_strided() {
+        let result: Vec<Vec<i32>> = (1..=10).windows(3, 4).collect();
+        assert_eq!(result, vec![vec![1, 2, 3], vec![5, 6, 7]]);
+    }
+
+    #[test]
+    fn test_windows_disjoint_matches_chunk() {
+        let result: Vec<Vec<i32>> = (1..=6).windows(2, 2).collect();
+        assert_eq!(result, crate::chunk(1..=6, 2));
+    }
+
+    #[test]
+    fn test_windows_partial() {
+        let result: Vec<Vec<i32>> = (1..=5).windows(3, 1).partial().collect();
+        assert_eq!(
+            result,
+            vec![
+                vec![1, 2, 3],
+                vec![2, 3, 4],
+                vec![3, 4, 5],
+                vec![4, 5],
+                vec![5]
+            ]
+        );
+
+        let result: Vec<Vec<i32>> = (1..=7).windows(2, 3).partial().collect();
+        assert_eq!(result, vec![vec![1, 2], vec![4, 5], vec![7]]);
+    }
+
+    #[test]
+    fn test_windows_shorter_than_size() {
+        assert_eq!((1..=2).windows(3, 1).count(), 0);
+        let result: Vec<Vec<i32>> = (1..=2).windows(3, 2).partial().collect();
+        assert_eq!(result, vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_windows_clones_owned_items() {
+        let words = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let result: Vec<Vec<String>> = words.into_iter().windows(2, 1).collect();
+        assert_eq!(result, vec![vec!["a", "b"], vec!["b", "c"]]);
+    }
+
+    #[test]
+    fn test_windows_size_hint() {
+        for (size, step, partial) in [(3, 1, false), (3, 1, true), (2, 3, false), (2, 3, true)] {
+            let mut iter = (1..8).windows(size, step);
+            if partial {
+                iter = iter.partial();
+            }
+            let mut expected = iter.clone().count();
+            assert_eq!(iter.len(), expected);
+            while iter.next().is_some() {
+                expected -= 1;
+                assert_eq!(iter.len(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn should_error_with_zero_arguments() {
+        assert!(matches!(try_windows(1..=3, 0, 1), Err(Error::ZeroSize)));
+        assert!(matches!((1..=3).try_windows(1, 0), Err(Error::ZeroStep)));
+    }
+
+    #[test]
+    fn test_size_hint_of_unbounded_source() {
+        let mut windows = windows(0u64.., 3, 1);
+        windows.next();
+        assert_eq!(windows.size_hint(), (usize::MAX - 2, None));
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::{Fuse, FusedIterator, Map};
+
+use crate::collection::MapCollection;
+
+/// A tuple of 2 to 8 iterables that can be zipped together.
+///
+/// This is implemented for tuples such as `(A, B)` or `(A, B, C, D)` where every
+/// element implements `IntoIterator`, and is what lets [`zip`], [`zip_longest`]
+/// and [`zip_with`] accept a varying number of inputs.
+pub trait ZipSources {
+    /// The tuple of iterators produced from the iterables.
+    type Iters;
+    /// The tuple of fused iterators produced from the iterables.
+    type FusedIters;
+
+    /// Converts every iterable into its iterator.
+    fn into_iters(self) -> Self::Iters;
+
+    /// Converts every iterable into its iterator, fused so it keeps returning
+    /// `None` once exhausted.
+    fn into_fused_iters(self) -> Self::FusedIters;
+}
+
+/// A tuple of collections that the items of an iterator over tuples can be
+/// split into, one collection per tuple position.
+///
+/// This is implemented for tuples of 2 to 8 collections that implement
+/// `Default + Extend`, such as `(Vec<A>, Vec<B>, HashSet<C>)`. See [`unzip`].
+pub trait Unzip<T>: Default {
+    /// Adds each element of `row` to the collection at the same position.
+    fn push_row(&mut self, row: T);
+}
+
+/// Lazily zips 2 to 8 iterables into an iterator of tuples, stopping as soon as
+/// any input is exhausted.
+///
+/// This generalizes `Iterator::zip`, which only takes two inputs, to a tuple of
+/// inputs, like es-toolkit's `zip`.
+///
+/// # Arguments
+///
+/// * `iterables` - A tuple of 2 to 8 iterables
+///
+/// # Returns
+///
+/// A [`Zip`] iterator yielding one tuple per position, as long as the shortest input
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::zip;
+///
+/// let ids = vec![1, 2, 3];
+/// let names = vec!["Alice", "Bob"];
+/// let admins = vec![true, false, true];
+///
+/// let rows: Vec<(i32, &str, bool)> = zip((ids, names, admins)).collect();
+/// assert_eq!(rows, vec![(1, "Alice", true), (2, "Bob", false)]);
+/// ```
+pub fn zip<Z: ZipSources>(iterables: Z) -> Zip<Z::Iters> {
+    Zip {
+        iters: iterables.into_iters(),
+    }
+}
+
+/// Lazily zips 2 to 8 iterables into an iterator of tuples of `Option`s,
+/// continuing until every input is exhausted.
+///
+/// Positions past the end of a shorter input are filled with `None`, where
+/// es-toolkit's `zip` would use `undefined`.
+///
+/// # Arguments
+///
+/// * `iterables` - A tuple of 2 to 8 iterables
+///
+/// # Returns
+///
+/// A [`ZipLongest`] iterator yielding one tuple per position, as long as the longest input
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::zip_longest;
+///
+/// let rows: Vec<_> = zip_longest((vec![1, 2], vec!['a'])).collect();
+/// assert_eq!(rows, vec![(Some(1), Some('a')), (Some(2), None)]);
+/// ```
+pub fn zip_longest<Z: ZipSources>(iterables: Z) -> ZipLongest<Z::FusedIters> {
+    ZipLongest {
+        iters: iterables.into_fused_iters(),
+    }
+}
+
+/// Lazily zips 2 to 8 iterables and combines each tuple with a function,
+/// stopping as soon as any input is exhausted.
+///
+/// This is the equivalent of es-toolkit's `zipWith`. The function receives the
+/// whole tuple, so its parameter usually needs a type annotation.
+///
+/// # Arguments
+///
+/// * `iterables` - A tuple of 2 to 8 iterables
+/// * `combine` - A function that turns one tuple into an output item
+///
+/// # Returns
+///
+/// An iterator yielding one combined item per position, as long as the shortest input
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::zip_with;
+///
+/// let totals: Vec<i32> = zip_with(
+///     (vec![1, 2], vec![10, 20], vec![100, 200]),
+///     |(a, b, c): (i32, i32, i32)| a + b + c,
+/// )
+/// .collect();
+/// assert_eq!(totals, vec![111, 222]);
+/// ```
+pub fn zip_with<Z, R, F>(iterables: Z, combine: F) -> Map<Zip<Z::Iters>, F>
+where
+    Z: ZipSources,
+    Zip<Z::Iters>: Iterator,
+    F: FnMut(<Zip<Z::Iters> as Iterator>::Item) -> R,
+{
+    zip(iterables).map(combine)
+}
+
+/// Splits an iterable of tuples into one collection per tuple position.
+///
+/// This generalizes `Iterator::unzip`, which only handles pairs, to tuples of 2 to
+/// 8 elements, like es-toolkit's `unzip`. The collection types are chosen by the
+/// caller and may differ per position.
+///
+/// # Arguments
+///
+/// * `items` - An iterable of tuples
+///
+/// # Returns
+///
+/// A tuple of collections `C`, each holding the elements at its position in input order
+///
+/// # Examples
+///
+/// ```rust
+/// use rust_toolkit::unzip;
+///
+/// let rows = vec![(1, 'a', true), (2, 'b', false)];
+/// let (ids, letters, flags): (Vec<i32>, String, Vec<bool>) = unzip(rows);
+/// assert_eq!(ids, vec![1, 2]);
+/// assert_eq!(letters, "ab");
+/// assert_eq!(flags, vec![true, false]);
+/// ```
+pub fn unzip<T, C>(items: impl IntoIterator<Item = T>) -> C
+where
+    C: Unzip<T>,
+{
+    let mut collections = C::default();
+    for row in items {
+        collections.push_row(row);
+    }
+    collections
+}
+
+/// Creates a HashMap by pairing each key with the value at the same position.
+///
+/// This is the equivalent of es-toolkit's `zipObject`, with one deliberate
+/// difference: es-toolkit maps keys past the end of `values` to `undefined`,
+/// whereas here those extra keys are dropped, as are extra values. To keep every
+/// key, map the values to `Some` and chain `std::iter::repeat(None)` to build a
+/// `HashMap<K, Option<V>>`. Later duplicate keys overwrite earlier ones, as in
+/// [`key_by`](crate::key_by).
+///
+/// # Arguments
+///
+/// * `keys` - An iterable of keys
+/// * `values` - An iterable of values
+///
+/// # Returns
+///
+/// A `HashMap<K, V>` from each key to its paired value
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use rust_toolkit::zip_object;
+///
+/// let settings = zip_object(["host", "port"], ["localhost", "8080"]);
+/// assert_eq!(settings, HashMap::from([("host", "localhost"), ("port", "8080")]));
+///
+/// // The key without a value is dropped rather than mapped to a missing value
+/// let partial = zip_object(["host", "port"], ["localhost"]);
+/// assert_eq!(partial, HashMap::from([("host", "localhost")]));
+///
+/// let values = ["localhost"].map(Some).into_iter().chain(std::iter::repeat(None));
+/// let padded = zip_object(["host", "port"], values);
+/// assert_eq!(padded, HashMap::from([("host", Some("localhost")), ("port", None)]));
+/// ```
+pub fn zip_object<K, V>(
+    keys: impl IntoIterator<Item = K>,
+    values: impl IntoIterator<Item = V>,
+) -> HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    zip_object_into(keys, values)
+}
+
+/// Creates any map type by pairing each key with the value at the same position.
+///
+/// This works like [`zip_object`], but the resulting map type `M` is chosen by the
+/// caller, like [`key_by_into`](crate::key_by_into). Pairs are added with
+/// [`MapCollection::insert`], so extra keys or values are dropped as in
+/// [`zip_object`].
+///
+/// # Arguments
+///
+/// * `keys` - An iterable of keys
+/// * `values` - An iterable of values
+///
+/// # Returns
+///
+/// A map of type `M` from each key to its paired value
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use rust_toolkit::zip_object_into;
+///
+/// let scores: BTreeMap<_, _> = zip_object_into(["bob", "alice"], [7, 9]);
+/// assert_eq!(scores.into_iter().collect::<Vec<_>>(), vec![("alice", 9), ("bob", 7)]);
+/// ```
+pub fn zip_object_into<M>(
+    keys: impl IntoIterator<Item = M::Key>,
+    values: impl IntoIterator<Item = M::Value>,
+) -> M
+where
+    M: MapCollection,
+{
+    let mut map = M::default();
+    for (key, value) in keys.into_iter().zip(values) {
+        map.insert(key, value);
+    }
+    map
+}
+
+/// An iterator that zips a tuple of iterators, stopping at the shortest.
+///
+/// This struct is created by [`zip`].
+#[derive(Debug, Clone)]
+pub struct Zip<T> {
+    iters: T,
+}
+
+/// An iterator that zips a tuple of fused iterators into tuples of `Option`s,
+/// stopping at the longest.
+///
+/// This struct is created by [`zip_longest`] or [`ZipExt::zip_longest`].
+#[derive(Debug, Clone)]
+pub struct ZipLongest<T> {
+    iters: T,
+}
+
+/// Combines the size hints of zipped iterators, keeping the smallest bounds.
+fn shortest_hint(hints: &[(usize, Option<usize>)]) -> (usize, Option<usize>) {
+    let lower = hints.iter().map(|&(lower, _)| lower).min().unwrap_or(0);
+    let upper = hints.iter().filter_map(|&(_, upper)| upper).min();
+    (lower, upper)
+}
+
+/// Combines the size hints of zipped iterators, keeping the largest bounds.
+fn longest_hint(hints: &[(usize, Option<usize>)]) -> (usize, Option<usize>) {
+    let lower = hints.iter().map(|&(lower, _)| lower).max().unwrap_or(0);
+    let upper = hints
+        .iter()
+        .map(|&(_, upper)| upper)
+        .try_fold(0, |max, upper| upper.map(|upper| max.max(upper)));
+    (lower, upper)
+}
+
+// Type parameters double as variable names when destructuring the tuples, which
+// keeps the macro free of a second list of identifiers.
+macro_rules! impl_zip {
+    ($($T:ident $C:ident),+) => {
+        impl<$($T: IntoIterator),+> ZipSources for ($($T,)+) {
+            type Iters = ($($T::IntoIter,)+);
+            type FusedIters = ($(Fuse<$T::IntoIter>,)+);
+
+            #[allow(non_snake_case)]
+            fn into_iters(self) -> Self::Iters {
+                let ($($T,)+) = self;
+                ($($T.into_iter(),)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn into_fused_iters(self) -> Self::FusedIters {
+                let ($($T,)+) = self;
+                ($($T.into_iter().fuse(),)+)
+            }
+        }
+
+        impl<$($T: Iterator),+> Iterator for Zip<($($T,)+)> {
+            type Item = ($($T::Item,)+);
+
+            #[allow(non_snake_case)]
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($($T,)+) = &mut self.iters;
+                Some(($($T.next()?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let ($($T,)+) = &self.iters;
+                shortest_hint(&[$($T.size_hint()),+])
+            }
+        }
+
+        impl<$($T: ExactSizeIterator),+> ExactSizeIterator for Zip<($($T,)+)> {}
+
+        impl<$($T: FusedIterator),+> FusedIterator for Zip<($($T,)+)> {}
+
+        impl<$($T: Iterator),+> Iterator for ZipLongest<($(Fuse<$T>,)+)> {
+            type Item = ($(Option<$T::Item>,)+);
+
+            #[allow(non_snake_case)]
+            fn next(&mut self) -> Option<Self::Item> {
+                let ($($T,)+) = &mut self.iters;
+                let ($($T,)+) = ($($T.next(),)+);
+                if $($T.is_none())&&+ {
+                    return None;
+                }
+                Some(($($T,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let ($($T,)+) = &self.iters;
+                longest_hint(&[$($T.size_hint()),+])
+            }
+        }
+
+        impl<$($T: ExactSizeIterator),+> ExactSizeIterator for ZipLongest<($(Fuse<$T>,)+)> {}
+
+        impl<$($T: Iterator),+> FusedIterator for ZipLongest<($(Fuse<$T>,)+)> {}
+
+        impl<$($T,)+ $($C),+> Unzip<($($T,)+)> for ($($C,)+)
+        where
+            $($C: Default + Extend<$T>),+
+        {
+            #[allow(non_snake_case)]
+            fn push_row(&mut self, row: ($($T,)+)) {
+                let ($($C,)+) = self;
+                let ($($T,)+) = row;
+                $($C.extend(std::iter::once($T));)+
+            }
+        }
+    };
+}
+
+impl_zip!(A CA, B CB);
+impl_zip!(A CA, B CB, C CC);
+impl_zip!(A CA, B CB, C CC, D CD);
+impl_zip!(A CA, B CB, C CC, D CD, E CE);
+impl_zip!(A CA, B CB, C CC, D CD, E CE, F CF);
+impl_zip!(A CA, B CB, C CC, D CD, E CE, F CF, G CG);
+impl_zip!(A CA, B CB, C CC, D CD, E CE, F CF, G CG, H CH);
+
+/// Extension trait that adds zipping methods to any iterator.
+pub trait ZipExt: Iterator {
+    /// Zips the iterator with another iterable into pairs of `Option`s, continuing
+    /// until both are exhausted.
+    ///
+    /// See [`zip_longest`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ZipExt;
+    ///
+    /// let pairs: Vec<_> = (1..=3).zip_longest("ab".chars()).collect();
+    /// assert_eq!(
+    ///     pairs,
+    ///     vec![(Some(1), Some('a')), (Some(2), Some('b')), (Some(3), None)]
+    /// );
+    /// ```
+    fn zip_longest<U>(self, other: U) -> ZipLongest<(Fuse<Self>, Fuse<U::IntoIter>)>
+    where
+        Self: Sized,
+        U: IntoIterator,
+    {
+        zip_longest((self, other))
+    }
+
+    /// Splits an iterator of tuples into one collection per tuple position.
+    ///
+    /// See [`unzip`] for details. This is named `unzip_n` to avoid clashing with
+    /// `Iterator::unzip`, which only handles pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rust_toolkit::ZipExt;
+    ///
+    /// let (a, b, c): (Vec<i32>, Vec<i32>, Vec<i32>) =
+    ///     (1..=3).map(|n| (n, n * 10, n * 100)).unzip_n();
+    /// assert_eq!(a, vec![1, 2, 3]);
+    /// assert_eq!(b, vec![10, 20, 30]);
+    /// assert_eq!(c, vec![100, 200, 300]);
+    /// ```
+    fn unzip_n<C>(self) -> C
+    where
+        Self: Sized,
+        C: Unzip<Self::Item>,
+    {
+        unzip(self)
+    }
+}
+
+impl<I: Iterator> ZipExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
+    use super::*;
+
+    #[test]
+    fn test_zip_stops_at_shortest() {
+        let rows: Vec<_> = zip((1..=4, "abc".chars(), vec![true; 5])).collect();
+        assert_eq!(rows, vec![(1, 'a', true), (2, 'b', true), (3, 'c', true)]);
+
+        let iter = zip((0..5, 0..3, 0..4));
+        assert_eq!(iter.len(), 3);
+    }
+
+    #[test]
+    fn test_zip_eight_inputs() {
+        let rows: Vec<_> = zip((0..2, 1..3, 2..4, 3..5, 4..6, 5..7, 6..8, 7..9)).collect();
+        assert_eq!(
+            rows,
+            vec![(0, 1, 2, 3, 4, 5, 6, 7), (1, 2, 3, 4, 5, 6, 7, 8)]
+        );
+    }
+
+    #[test]
+    fn test_zip_longest_pads_with_none() {
+        let rows: Vec<_> = zip_longest((vec![1], vec!["a", "b"], Vec::<bool>::new())).collect();
+        assert_eq!(
+            rows,
+            vec![(Some(1), Some("a"), None), (None, Some("b"), None)]
+        );
+
+        let iter = zip_longest((0..2, 0..5));
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+        assert_eq!(iter.count(), 5);
+
+        assert_eq!((0..0).zip_longest(0..0).next(), None);
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let labels: Vec<String> = zip_with((["x", "y"], [1, 2]), |(name, n): (&str, i32)| {
+            format!("{name}{n}")
+        })
+        .collect();
+        assert_eq!(labels, vec!["x1", "y2"]);
+    }
+
+    #[test]
+    fn test_unzip_into_mixed_collections() {
+        let rows = vec![(1, "a", 'x', 1.5), (2, "b", 'y', 2.5), (1, "c", 'z', 3.5)];
+        let (ids, names, letters, weights): (HashSet<i32>, Vec<&str>, String, Vec<f64>) =
+            unzip(rows.clone());
+        assert_eq!(ids, HashSet::from([1, 2]));
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert_eq!(letters, "xyz");
+        assert_eq!(weights, vec![1.5, 2.5, 3.5]);
+
+        let (a, b, c, d): (Vec<_>, Vec<_>, Vec<_>, Vec<_>) = rows.into_iter().unzip_n();
+        assert_eq!((a.len(), b.len(), c.len(), d.len()), (3, 3, 3, 3));
+    }
+
+    #[test]
+    fn test_zip_object() {
+        let map = zip_object(vec!["a", "b", "a", "c"], vec![1, 2, 3]);
+        assert_eq!(map, HashMap::from([("a", 3), ("b", 2)]));
+
+        let map: BTreeMap<i32, char> = zip_object_into(vec![2, 1], "xy".chars());
+        assert_eq!(map, BTreeMap::from([(1, 'y'), (2, 'x')]));
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// A key-value map that the `*_into` functions can collect into.
+///
+/// This is implemented for `HashMap<K, V, S>` with any `BuildHasher` and for
+/// `BTreeMap<K, V>`, so callers can pick sorted keys or a custom hasher instead of
+/// the default `HashMap`.
+pub trait MapCollection: Default {
+    /// The type of keys in the map.
+    type Key;
+    /// The type of values in the map.
+    type Value;
+
+    /// Returns a mutable reference to the value for `key`, inserting the result of
+    /// `default` first if the key is missing.
+    fn get_or_insert_with(
+        &mut self,
+        key: Self::Key,
+        default: impl FnOnce() -> Self::Value,
+    ) -> &mut Self::Value;
+
+    /// Inserts a value for `key`, returning the previous value if there was one.
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value>;
+}
+
+impl<K, V, S> MapCollection for HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        self.entry(key).or_insert_with(default)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+}
+
+impl<K, V> MapCollection for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        self.entry(key).or_insert_with(default)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+}
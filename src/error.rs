@@ -0,0 +1,93 @@
+use std::fmt;
+
+/// Errors returned by the fallible functions in this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A size argument was `0` where a positive size is required.
+    ZeroSize,
+    /// A step argument was `0` where a positive step is required.
+    ZeroStep,
+    /// An item's weight exceeded the maximum weight of a chunk.
+    WeightExceeded {
+        /// The position of the offending item in the input.
+        index: usize,
+        /// The weight of the offending item.
+        weight: usize,
+        /// The maximum weight allowed per chunk.
+        max_weight: usize,
+    },
+    /// A HyperLogLog precision was outside the supported range.
+    InvalidPrecision {
+        /// The rejected precision.
+        precision: u8,
+        /// The smallest supported precision.
+        min: u8,
+        /// The largest supported precision.
+        max: u8,
+    },
+    /// Two sketches could not be merged because they were built with different parameters.
+    SketchMismatch,
+    /// Bytes could not be decoded into a sketch.
+    InvalidSketchBytes,
+    /// Sketch dimensions were too large to allocate or to serialize.
+    SketchTooLarge {
+        /// The rejected width.
+        width: usize,
+        /// The rejected depth.
+        depth: usize,
+    },
+    /// An item was routed to a bucket that does not exist.
+    BucketOutOfRange {
+        /// The position of the offending item in the input.
+        index: usize,
+        /// The bucket the item was routed to.
+        bucket: usize,
+        /// The number of available buckets.
+        buckets: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ZeroSize => write!(f, "size must be greater than 0"),
+            Error::ZeroStep => write!(f, "step must be greater than 0"),
+            Error::WeightExceeded {
+                index,
+                weight,
+                max_weight,
+            } => write!(
+                f,
+                "item at index {index} has weight {weight}, which exceeds the maximum of {max_weight}"
+            ),
+            Error::InvalidPrecision {
+                precision,
+                min,
+                max,
+            } => write!(
+                f,
+                "precision must be between {min} and {max}, got {precision}"
+            ),
+            Error::SketchMismatch => write!(f, "sketches were built with different parameters"),
+            Error::InvalidSketchBytes => write!(f, "bytes are not a valid serialized sketch"),
+            Error::SketchTooLarge { width, depth } => write!(
+                f,
+                "sketch of width {width} and depth {depth} exceeds the supported size"
+            ),
+            Error::BucketOutOfRange {
+                index,
+                bucket,
+                buckets,
+            } => write!(
+                f,
+                "item at index {index} was routed to bucket {bucket}, but there are only {buckets} buckets"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A `Result` alias using the crate-level [`Error`] type.
+pub type Result<T> = std::result::Result<T, Error>;
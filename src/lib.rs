@@ -4,6 +4,10 @@
 //! This library provides type-safe, performant utility functions for common programming tasks.
 
 pub mod array;
+pub mod collection;
+pub mod error;
 
 // Re-export commonly used functions
 pub use array::*;
+pub use collection::MapCollection;
+pub use error::{Error, Result};